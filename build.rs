@@ -1,3 +1,7 @@
+use std::env;
+
 fn main() {
-    println!("cargo:rustc-flags=-l sctp");
+    if env::var_os("CARGO_FEATURE_LIBSCTP").is_some() {
+        println!("cargo:rustc-flags=-l sctp");
+    }
 }
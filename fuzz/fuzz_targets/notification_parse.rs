@@ -0,0 +1,8 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+use sctp::net::notification::notification_parse;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = notification_parse(data);
+});
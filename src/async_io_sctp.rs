@@ -0,0 +1,103 @@
+//! async-io versions of the SCTP sockets (smol, async-std), driven by `async_io::Async`.
+//!
+//! The wrapped socket stays reachable through `get_ref` for the synchronous options.
+
+use std::io::{self, Read, Write};
+use std::net::Shutdown;
+use std::os::unix::io::AsFd;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use async_io::Async;
+use futures_io::{AsyncRead, AsyncWrite};
+
+use sctp::{SctpStream, SctpListener, SctpEndpoint};
+use async_sctp::Registered;
+
+impl<T: AsFd> Registered for Async<T> {
+    type Socket = T;
+
+    fn register(socket: T) -> io::Result<Async<T>> {
+        Async::new(socket)
+    }
+
+    fn get_ref(&self) -> &T {
+        Async::get_ref(self)
+    }
+
+    fn poll_writable(&self, cx: &mut Context) -> Poll<io::Result<()>> {
+        Async::poll_writable(self, cx)
+    }
+
+    fn poll_read_with<R, F>(&self, cx: &mut Context, mut f: F) -> Poll<io::Result<R>>
+        where F: FnMut(&T) -> io::Result<R>
+    {
+        loop {
+            match f(self.get_ref()) {
+                Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => (),
+                result => return Poll::Ready(result)
+            }
+
+            ready!(self.poll_readable(cx))?;
+        }
+    }
+
+    fn poll_write_with<R, F>(&self, cx: &mut Context, mut f: F) -> Poll<io::Result<R>>
+        where F: FnMut(&T) -> io::Result<R>
+    {
+        loop {
+            match f(self.get_ref()) {
+                Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => (),
+                result => return Poll::Ready(result)
+            }
+
+            ready!(Async::poll_writable(self, cx))?;
+        }
+    }
+}
+
+pub struct AsyncSctpStream(Async<SctpStream>);
+
+pub struct AsyncSctpListener(Async<SctpListener>);
+
+pub struct AsyncSctpEndpoint(Async<SctpEndpoint>);
+
+impl_async_sctp! { AsyncSctpStream, AsyncSctpListener, AsyncSctpEndpoint }
+
+impl AsyncSctpStream {
+    pub fn into_inner(self) -> io::Result<SctpStream> {
+        self.0.into_inner()
+    }
+}
+
+impl AsyncRead for AsyncSctpStream {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        self.0.poll_read_with(cx, |mut stream| stream.read(buf))
+    }
+}
+
+impl AsyncWrite for AsyncSctpStream {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<io::Result<usize>> {
+        self.0.poll_write_with(cx, |mut stream| stream.write(buf))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &mut Context) -> Poll<io::Result<()>> {
+        Poll::Ready(self.get_ref().shutdown(Shutdown::Write))
+    }
+}
+
+impl AsyncSctpListener {
+    pub fn into_inner(self) -> io::Result<SctpListener> {
+        self.0.into_inner()
+    }
+}
+
+impl AsyncSctpEndpoint {
+    pub fn into_inner(self) -> io::Result<SctpEndpoint> {
+        self.0.into_inner()
+    }
+}
@@ -0,0 +1,174 @@
+//! The parts of the `tokio` and `async-io` wrappers that don't depend on the runtime.
+//!
+//! Each runtime module implements `Registered` for its reactor handle and declares the
+//! wrapper types, `impl_async_sctp!` then adds the methods they have in common.
+
+use std::future::{self, Future};
+use std::io;
+use std::task::{ready, Context, Poll};
+
+use sctp::SctpStream;
+
+/// A socket registered with a runtime's reactor
+pub trait Registered: Sized {
+    type Socket;
+
+    fn register(socket: Self::Socket) -> io::Result<Self>;
+
+    fn get_ref(&self) -> &Self::Socket;
+
+    fn poll_writable(&self, cx: &mut Context) -> Poll<io::Result<()>>;
+
+    /// Run `f` once the socket is readable, until it stops failing with `WouldBlock`
+    fn poll_read_with<R, F>(&self, cx: &mut Context, f: F) -> Poll<io::Result<R>>
+        where F: FnMut(&Self::Socket) -> io::Result<R>;
+
+    /// Run `f` once the socket is writable, until it stops failing with `WouldBlock`
+    fn poll_write_with<R, F>(&self, cx: &mut Context, f: F) -> Poll<io::Result<R>>
+        where F: FnMut(&Self::Socket) -> io::Result<R>;
+}
+
+/// Finish a non-blocking connect. The association setup completes once the socket turns
+/// writable, its pending error tells whether it succeeded.
+pub fn connecting<T, S, F>(stream: io::Result<SctpStream>, wrap: F) -> impl Future<Output = io::Result<S>>
+    where T: Registered<Socket = SctpStream>, F: FnOnce(T) -> S
+{
+    let mut io = Some(stream.and_then(T::register));
+    let mut wrap = Some(wrap);
+
+    future::poll_fn(move |cx| {
+        if let Some(Ok(ref io)) = io {
+            ready!(io.poll_writable(cx))?;
+        }
+
+        let io = io.take().expect("connect polled after completion")?;
+
+        if let Some(e) = io.get_ref().take_error()? {
+            return Poll::Ready(Err(e))
+        }
+
+        let wrap = wrap.take().expect("connect polled after completion");
+        Poll::Ready(Ok(wrap(io)))
+    })
+}
+
+macro_rules! impl_async_sctp {
+    ($stream:ident, $listener:ident, $endpoint:ident) => (
+        impl $stream {
+            /// Wrap a connected stream, switching it to non-blocking mode
+            pub fn new(stream: $crate::SctpStream) -> ::std::io::Result<$stream> {
+                stream.set_nonblocking(true)?;
+                Ok($stream($crate::async_sctp::Registered::register(stream)?))
+            }
+
+            pub fn connect<A: ::std::net::ToSocketAddrs>(addr: A) -> impl ::std::future::Future<Output = ::std::io::Result<$stream>> {
+                $crate::async_sctp::connecting($crate::SctpStream::connect_nonblocking(addr), $stream)
+            }
+
+            pub fn connectx<A: ::std::net::ToSocketAddrs>(addrs: &[A]) -> impl ::std::future::Future<Output = ::std::io::Result<$stream>> {
+                $crate::async_sctp::connecting($crate::SctpStream::connectx_nonblocking(addrs), $stream)
+            }
+
+            pub fn poll_sendmsg_with(&self, cx: &mut ::std::task::Context, msg: &[u8], options: &$crate::SendOptions) -> ::std::task::Poll<::std::io::Result<usize>> {
+                $crate::async_sctp::Registered::poll_write_with(&self.0, cx, |stream| stream.sendmsg_with(msg, options))
+            }
+
+            pub fn sendmsg<'a>(&'a self, msg: &'a [u8], stream: u16) -> impl ::std::future::Future<Output = ::std::io::Result<usize>> + 'a {
+                let options = $crate::SendOptions::new().stream(stream);
+                ::std::future::poll_fn(move |cx| self.poll_sendmsg_with(cx, msg, &options))
+            }
+
+            pub fn sendmsg_with<'a>(&'a self, msg: &'a [u8], options: &'a $crate::SendOptions) -> impl ::std::future::Future<Output = ::std::io::Result<usize>> + 'a {
+                ::std::future::poll_fn(move |cx| self.poll_sendmsg_with(cx, msg, options))
+            }
+
+            pub fn poll_recvmsg(&self, cx: &mut ::std::task::Context, msg: &mut [u8]) -> ::std::task::Poll<::std::io::Result<(usize, $crate::RecvInfo, Option<$crate::Notification>)>> {
+                $crate::async_sctp::Registered::poll_read_with(&self.0, cx, |stream| stream.recvmsg(msg))
+            }
+
+            pub fn recvmsg<'a>(&'a self, msg: &'a mut [u8]) -> impl ::std::future::Future<Output = ::std::io::Result<(usize, $crate::RecvInfo, Option<$crate::Notification>)>> + 'a {
+                ::std::future::poll_fn(move |cx| self.poll_recvmsg(cx, msg))
+            }
+
+            pub fn get_ref(&self) -> &$crate::SctpStream {
+                $crate::async_sctp::Registered::get_ref(&self.0)
+            }
+        }
+
+        impl $listener {
+            /// Wrap a listening socket, switching it to non-blocking mode
+            pub fn new(listener: $crate::SctpListener) -> ::std::io::Result<$listener> {
+                listener.set_nonblocking(true)?;
+                Ok($listener($crate::async_sctp::Registered::register(listener)?))
+            }
+
+            pub fn bind<A: ::std::net::ToSocketAddrs>(addr: A) -> ::std::io::Result<$listener> {
+                $listener::new($crate::SctpListener::bind(addr)?)
+            }
+
+            pub fn bindx<A: ::std::net::ToSocketAddrs>(addrs: &[A]) -> ::std::io::Result<$listener> {
+                $listener::new($crate::SctpListener::bindx(addrs)?)
+            }
+
+            pub fn poll_accept(&self, cx: &mut ::std::task::Context) -> ::std::task::Poll<::std::io::Result<($stream, ::std::net::SocketAddr)>> {
+                let (stream, addr) = ::std::task::ready!($crate::async_sctp::Registered::poll_read_with(&self.0, cx, |listener| listener.accept()))?;
+                ::std::task::Poll::Ready($stream::new(stream).map(|stream| (stream, addr)))
+            }
+
+            pub fn accept<'a>(&'a self) -> impl ::std::future::Future<Output = ::std::io::Result<($stream, ::std::net::SocketAddr)>> + 'a {
+                ::std::future::poll_fn(move |cx| self.poll_accept(cx))
+            }
+
+            pub fn get_ref(&self) -> &$crate::SctpListener {
+                $crate::async_sctp::Registered::get_ref(&self.0)
+            }
+        }
+
+        impl $endpoint {
+            /// Wrap a one-to-many socket, switching it to non-blocking mode
+            pub fn new(endpoint: $crate::SctpEndpoint) -> ::std::io::Result<$endpoint> {
+                endpoint.set_nonblocking(true)?;
+                Ok($endpoint($crate::async_sctp::Registered::register(endpoint)?))
+            }
+
+            pub fn bind<A: ::std::net::ToSocketAddrs>(addr: A) -> ::std::io::Result<$endpoint> {
+                $endpoint::new($crate::SctpEndpoint::bind(addr)?)
+            }
+
+            pub fn bindx<A: ::std::net::ToSocketAddrs>(addrs: &[A]) -> ::std::io::Result<$endpoint> {
+                $endpoint::new($crate::SctpEndpoint::bindx(addrs)?)
+            }
+
+            pub fn poll_send_to_with(&self, cx: &mut ::std::task::Context, msg: &[u8], addr: &::std::net::SocketAddr, options: &$crate::SendOptions) -> ::std::task::Poll<::std::io::Result<usize>> {
+                $crate::async_sctp::Registered::poll_write_with(&self.0, cx, |endpoint| endpoint.send_to_with(msg, addr, options))
+            }
+
+            pub fn send_to<'a>(&'a self, msg: &'a [u8], addr: ::std::net::SocketAddr, stream: u16) -> impl ::std::future::Future<Output = ::std::io::Result<usize>> + 'a {
+                let options = $crate::SendOptions::new().stream(stream);
+                ::std::future::poll_fn(move |cx| self.poll_send_to_with(cx, msg, &addr, &options))
+            }
+
+            pub fn send_to_with<'a>(&'a self, msg: &'a [u8], addr: ::std::net::SocketAddr, options: &'a $crate::SendOptions) -> impl ::std::future::Future<Output = ::std::io::Result<usize>> + 'a {
+                ::std::future::poll_fn(move |cx| self.poll_send_to_with(cx, msg, &addr, options))
+            }
+
+            #[allow(clippy::type_complexity)]
+            pub fn poll_recv_from(&self, cx: &mut ::std::task::Context, msg: &mut [u8]) -> ::std::task::Poll<::std::io::Result<(usize, $crate::RecvInfo, Option<::std::net::SocketAddr>, Option<$crate::Notification>)>> {
+                $crate::async_sctp::Registered::poll_read_with(&self.0, cx, |endpoint| endpoint.revc_from(msg))
+            }
+
+            pub fn recv_from<'a>(&'a self, msg: &'a mut [u8]) -> impl ::std::future::Future<Output = ::std::io::Result<(usize, $crate::RecvInfo, Option<::std::net::SocketAddr>, Option<$crate::Notification>)>> + 'a {
+                ::std::future::poll_fn(move |cx| self.poll_recv_from(cx, msg))
+            }
+
+            /// Branch an association off into its own one-to-one stream
+            pub fn peeloff(&self, assoc: $crate::AssocId) -> ::std::io::Result<$stream> {
+                $stream::new(self.get_ref().peeloff(assoc)?)
+            }
+
+            pub fn get_ref(&self) -> &$crate::SctpEndpoint {
+                $crate::async_sctp::Registered::get_ref(&self.0)
+            }
+        }
+    )
+}
@@ -1,11 +1,39 @@
 extern crate libc;
+#[cfg(feature = "mio")]
+extern crate mio;
+#[cfg(feature = "tokio")]
+extern crate tokio;
+#[cfg(feature = "async-io")]
+extern crate async_io;
+#[cfg(feature = "async-io")]
+extern crate futures_io;
 
 pub use sctp::SctpListener;
 pub use sctp::SctpStream;
 pub use sctp::SctpEndpoint;
+pub use sctp::SctpSocketBuilder;
 pub use net::event::Event;
+pub use net::notification::Notification;
+pub use net::assoc::AssocId;
+pub use net::info::{RecvInfo, NxtInfo, PrPolicy, SendOptions};
+pub use net::message::MessageBuf;
+pub use net::opt::{InitParams, RtoInfo, AssocParams, PeerAddrParams, HmacAlgo};
+pub use net::status::{AssocStatus, AssocState, PathInfo, PathState, AssocStats};
 
 #[allow(dead_code)]
 pub mod sys;
 pub mod net;
 pub mod sctp;
+
+#[cfg(feature = "mio")]
+mod source;
+
+#[cfg(any(feature = "tokio", feature = "async-io"))]
+#[macro_use]
+mod async_sctp;
+
+#[cfg(feature = "tokio")]
+pub mod tokio_sctp;
+
+#[cfg(feature = "async-io")]
+pub mod async_io_sctp;
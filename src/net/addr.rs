@@ -1,43 +1,64 @@
-use std::net::{SocketAddr, SocketAddrV4, SocketAddrV6, ToSocketAddrs};
+use std::net::{SocketAddr, SocketAddrV4, SocketAddrV6, Ipv4Addr, Ipv6Addr, ToSocketAddrs};
 use std::mem;
+use std::ptr;
 use std::io::{self, Error, ErrorKind};
 
 use libc;
 
 use net::{FromInner, IntoInner};
 
-struct SocketAddrV42 {
-    pub inner: libc::sockaddr_in
+#[repr(C)]
+pub union SocketAddrCRepr {
+    v4: libc::sockaddr_in,
+    v6: libc::sockaddr_in6
 }
 
-struct SocketAddrV62 {
-    pub inner: libc::sockaddr_in6
+impl SocketAddrCRepr {
+    pub fn as_ptr(&self) -> *const libc::sockaddr {
+        self as *const _ as *const libc::sockaddr
+    }
 }
 
 impl FromInner<libc::sockaddr_in> for SocketAddrV4 {
     fn from_inner(addr: libc::sockaddr_in) -> SocketAddrV4 {
-        unsafe {
-            mem::transmute(SocketAddrV42 { inner: addr })
-        }
+        SocketAddrV4::new(
+            Ipv4Addr::from(u32::from_be(addr.sin_addr.s_addr)),
+            u16::from_be(addr.sin_port)
+        )
     }
 }
 
 impl FromInner<libc::sockaddr_in6> for SocketAddrV6 {
     fn from_inner(addr: libc::sockaddr_in6) -> SocketAddrV6 {
-        unsafe {
-            mem::transmute(SocketAddrV62 { inner: addr })
-        }
+        SocketAddrV6::new(
+            Ipv6Addr::from(addr.sin6_addr.s6_addr),
+            u16::from_be(addr.sin6_port),
+            addr.sin6_flowinfo,
+            addr.sin6_scope_id
+        )
     }
 }
 
-impl<'a> IntoInner<(*const libc::sockaddr, libc::socklen_t)> for &'a SocketAddr {
-    fn into_inner(self) -> (*const libc::sockaddr, libc::socklen_t) {
+impl IntoInner<(SocketAddrCRepr, libc::socklen_t)> for &SocketAddr {
+    fn into_inner(self) -> (SocketAddrCRepr, libc::socklen_t) {
         match *self {
             SocketAddr::V4(ref a) => {
-                (a as *const _ as *const _, mem::size_of_val(a) as libc::socklen_t)
+                let mut sockaddr: libc::sockaddr_in = unsafe { mem::zeroed() };
+                sockaddr.sin_family = libc::AF_INET as libc::sa_family_t;
+                sockaddr.sin_port = a.port().to_be();
+                sockaddr.sin_addr = libc::in_addr { s_addr: u32::from(*a.ip()).to_be() };
+
+                (SocketAddrCRepr { v4: sockaddr }, mem::size_of::<libc::sockaddr_in>() as libc::socklen_t)
             }
             SocketAddr::V6(ref a) => {
-                (a as *const _ as *const _, mem::size_of_val(a) as libc::socklen_t)
+                let mut sockaddr: libc::sockaddr_in6 = unsafe { mem::zeroed() };
+                sockaddr.sin6_family = libc::AF_INET6 as libc::sa_family_t;
+                sockaddr.sin6_port = a.port().to_be();
+                sockaddr.sin6_addr = libc::in6_addr { s6_addr: a.ip().octets() };
+                sockaddr.sin6_flowinfo = a.flowinfo();
+                sockaddr.sin6_scope_id = a.scope_id();
+
+                (SocketAddrCRepr { v6: sockaddr }, mem::size_of::<libc::sockaddr_in6>() as libc::socklen_t)
             }
         }
     }
@@ -46,23 +67,56 @@ impl<'a> IntoInner<(*const libc::sockaddr, libc::socklen_t)> for &'a SocketAddr
 pub fn sockaddr_to_addr(storage: &libc::sockaddr_storage, len: usize) -> io::Result<SocketAddr> {
     match storage.ss_family as libc::c_int {
         libc::AF_INET => {
-            assert!(len as usize >= mem::size_of::<libc::sockaddr_in>());
+            if len < mem::size_of::<libc::sockaddr_in>() {
+                return Err(Error::new(ErrorKind::InvalidInput, "Address is too short"))
+            }
             Ok(SocketAddr::V4(FromInner::from_inner(unsafe {
                 *(storage as *const _ as *const libc::sockaddr_in)
             })))
         }
         libc::AF_INET6 => {
-            assert!(len as usize >= mem::size_of::<libc::sockaddr_in6>());
+            if len < mem::size_of::<libc::sockaddr_in6>() {
+                return Err(Error::new(ErrorKind::InvalidInput, "Address is too short"))
+            }
             Ok(SocketAddr::V6(FromInner::from_inner(unsafe {
                 *(storage as *const _ as *const libc::sockaddr_in6)
             })))
         }
         _ => {
-            Err(Error::new(ErrorKind::Other, "Cannot retrieve addresses"))
+            Err(Error::other("Cannot retrieve addresses"))
         }
     }
 }
 
+pub fn addr_to_storage(addr: &SocketAddr) -> libc::sockaddr_storage {
+    let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+    let (addrp, len) = addr.into_inner();
+
+    unsafe {
+        ptr::copy_nonoverlapping(
+            addrp.as_ptr() as *const u8,
+            &mut storage as *mut libc::sockaddr_storage as *mut u8,
+            len as usize
+        );
+    }
+
+    storage
+}
+
 pub fn parse_addr<A: ToSocketAddrs>(addr: A) -> io::Result<SocketAddr> {
     addr.to_socket_addrs()?.next().ok_or(Error::new(ErrorKind::InvalidInput, "Address is not valid"))
 }
+
+pub fn parse_addrs<A: ToSocketAddrs>(addrs: &[A]) -> io::Result<Vec<SocketAddr>> {
+    if addrs.is_empty() {
+        return Err(Error::new(ErrorKind::InvalidInput, "No addresses given"))
+    }
+
+    let mut addrs2 = Vec::with_capacity(addrs.len());
+
+    for addr in addrs {
+        addrs2.push(parse_addr(addr)?);
+    }
+
+    Ok(addrs2)
+}
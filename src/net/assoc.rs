@@ -0,0 +1,23 @@
+use sys;
+
+#[derive(Debug, Copy, PartialEq, Eq, Clone, PartialOrd, Ord, Hash)]
+pub struct AssocId(sys::sctp_assoc_t);
+
+impl AssocId {
+    /// Socket wide settings, also inherited by associations set up later
+    #[inline]
+    pub fn future() -> AssocId {
+        AssocId(sys::SCTP_FUTURE_ASSOC)
+    }
+
+    #[inline]
+    pub fn as_i32(&self) -> i32 {
+        self.0
+    }
+}
+
+impl From<i32> for AssocId {
+    fn from(id: i32) -> AssocId {
+        AssocId(id)
+    }
+}
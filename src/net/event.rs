@@ -1,15 +1,20 @@
 use std::ops;
 
-const DATA_IO: usize            = 0b0000000001;
-const ASSOCIATION: usize        = 0b0000000010;
-const ADDRESS: usize            = 0b0000000100;
-const SEND_FAILURE: usize       = 0b0000001000;
-const PEER_ERROR: usize         = 0b0000010000;
-const SHUTDOWN: usize           = 0b0000100000;
-const PARTIAL_DELIVERY: usize   = 0b0001000000;
-const ADAPTATION_LAYER: usize   = 0b0010000000;
-const AUTHENTICATION: usize     = 0b0100000000;
-const SENDER_DRY: usize         = 0b1000000000;
+const DATA_IO: usize            = 0b00000000000001;
+const ASSOCIATION: usize        = 0b00000000000010;
+const ADDRESS: usize            = 0b00000000000100;
+const SEND_FAILURE: usize       = 0b00000000001000;
+const PEER_ERROR: usize         = 0b00000000010000;
+const SHUTDOWN: usize           = 0b00000000100000;
+const PARTIAL_DELIVERY: usize   = 0b00000001000000;
+const ADAPTATION_LAYER: usize   = 0b00000010000000;
+const AUTHENTICATION: usize     = 0b00000100000000;
+const SENDER_DRY: usize         = 0b00001000000000;
+const STREAM_RESET: usize       = 0b00010000000000;
+const ASSOC_RESET: usize        = 0b00100000000000;
+const STREAM_CHANGE: usize      = 0b01000000000000;
+const SEND_FAILURE_EVENT: usize = 0b10000000000000;
+const ALL: usize                = 0b11111111111111;
 
 #[derive(Debug, Copy, PartialEq, Eq, Clone, PartialOrd, Ord)]
 pub struct Event(usize);
@@ -20,6 +25,12 @@ impl Event {
         Event(0)
     }
 
+    /// Every event the kernel can notify about
+    #[inline]
+    pub fn all() -> Event {
+        Event(ALL)
+    }
+
     #[inline]
     pub fn data_io() -> Event {
         Event(DATA_IO)
@@ -70,6 +81,28 @@ impl Event {
         Event(SENDER_DRY)
     }
 
+    #[inline]
+    pub fn stream_reset() -> Event {
+        Event(STREAM_RESET)
+    }
+
+    #[inline]
+    pub fn assoc_reset() -> Event {
+        Event(ASSOC_RESET)
+    }
+
+    #[inline]
+    pub fn stream_change() -> Event {
+        Event(STREAM_CHANGE)
+    }
+
+    /// The `SCTP_SEND_FAILED_EVENT` notification, reporting the `sctp_sndinfo` of the
+    /// message instead of the deprecated `sctp_sndrcvinfo`
+    #[inline]
+    pub fn send_failure_event() -> Event {
+        Event(SEND_FAILURE_EVENT)
+    }
+
     #[inline]
     pub fn insert(&mut self, other: Event) {
         self.0 |= other.0
@@ -13,12 +13,12 @@ pub struct FileDesc {
 }
 
 pub fn max_len() -> usize {
-    <libc::ssize_t>::max_value() as usize
+    <libc::ssize_t>::MAX as usize
 }
 
 impl FileDesc {
     pub fn new(fd: libc::c_int) -> FileDesc {
-        FileDesc { fd: fd }
+        FileDesc { fd }
     }
 
     pub fn raw(&self) -> libc::c_int {
@@ -89,7 +89,7 @@ impl FileDesc {
     }
 }
 
-impl<'a> Read for &'a FileDesc {
+impl Read for &FileDesc {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         (**self).read(buf)
     }
@@ -0,0 +1,339 @@
+use std::mem;
+use std::time::Duration;
+
+use libc;
+
+use sys;
+
+use net::assoc::AssocId;
+use net::opt::millis;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecvInfo {
+    stream: u16,
+    ssn: u16,
+    flags: u16,
+    ppid: u32,
+    context: u32,
+    tsn: u32,
+    cumtsn: u32,
+    assoc_id: AssocId,
+    msg_flags: libc::c_int
+}
+
+impl RecvInfo {
+    #[inline]
+    pub fn stream(&self) -> u16 {
+        self.stream
+    }
+
+    #[inline]
+    pub fn ssn(&self) -> u16 {
+        self.ssn
+    }
+
+    #[inline]
+    pub fn flags(&self) -> u16 {
+        self.flags
+    }
+
+    /// Payload protocol identifier, exactly as set by the sender (no byte order conversion)
+    #[inline]
+    pub fn ppid(&self) -> u32 {
+        self.ppid
+    }
+
+    #[inline]
+    pub fn context(&self) -> u32 {
+        self.context
+    }
+
+    #[inline]
+    pub fn tsn(&self) -> u32 {
+        self.tsn
+    }
+
+    #[inline]
+    pub fn cumtsn(&self) -> u32 {
+        self.cumtsn
+    }
+
+    #[inline]
+    pub fn assoc_id(&self) -> AssocId {
+        self.assoc_id
+    }
+
+    #[inline]
+    pub fn unordered(&self) -> bool {
+        self.flags & sys::sctp_sinfo_flags::SCTP_UNORDERED as u16 != 0
+    }
+
+    /// The read completed a message (`MSG_EOR`), otherwise more of it is still to be read
+    #[inline]
+    pub fn eor(&self) -> bool {
+        self.msg_flags & libc::MSG_EOR != 0
+    }
+
+    /// The message did not fit into the buffer and was cut (`MSG_TRUNC`)
+    #[inline]
+    pub fn truncated(&self) -> bool {
+        self.msg_flags & libc::MSG_TRUNC != 0
+    }
+
+    /// The control messages did not fit and were cut (`MSG_CTRUNC`), so `recvv` may have
+    /// dropped the SCTP_RCVINFO or SCTP_NXTINFO of the message
+    #[inline]
+    pub fn control_truncated(&self) -> bool {
+        self.msg_flags & libc::MSG_CTRUNC != 0
+    }
+
+    #[inline]
+    pub fn is_notification(&self) -> bool {
+        self.msg_flags & sys::MSG_NOTIFICATION != 0
+    }
+}
+
+impl From<sys::sctp_sndrcvinfo> for RecvInfo {
+    fn from(info: sys::sctp_sndrcvinfo) -> RecvInfo {
+        RecvInfo {
+            stream: info.sinfo_stream,
+            ssn: info.sinfo_ssn,
+            flags: info.sinfo_flags,
+            ppid: info.sinfo_ppid,
+            context: info.sinfo_context,
+            tsn: info.sinfo_tsn,
+            cumtsn: info.sinfo_cumtsn,
+            assoc_id: AssocId::from(info.sinfo_assoc_id),
+            msg_flags: 0
+        }
+    }
+}
+
+impl From<(sys::sctp_sndrcvinfo, libc::c_int)> for RecvInfo {
+    fn from((info, msg_flags): (sys::sctp_sndrcvinfo, libc::c_int)) -> RecvInfo {
+        let mut info = RecvInfo::from(info);
+        info.msg_flags = msg_flags;
+        info
+    }
+}
+
+impl From<(sys::sctp_rcvinfo, libc::c_int)> for RecvInfo {
+    fn from((info, msg_flags): (sys::sctp_rcvinfo, libc::c_int)) -> RecvInfo {
+        RecvInfo {
+            stream: info.rcv_sid,
+            ssn: info.rcv_ssn,
+            flags: info.rcv_flags,
+            ppid: info.rcv_ppid,
+            context: info.rcv_context,
+            tsn: info.rcv_tsn,
+            cumtsn: info.rcv_cumtsn,
+            assoc_id: AssocId::from(info.rcv_assoc_id),
+            msg_flags
+        }
+    }
+}
+
+/// Description of the message queued after the one just read (`SCTP_NXTINFO`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NxtInfo {
+    stream: u16,
+    flags: u16,
+    ppid: u32,
+    length: u32,
+    assoc_id: AssocId
+}
+
+impl NxtInfo {
+    #[inline]
+    pub fn stream(&self) -> u16 {
+        self.stream
+    }
+
+    #[inline]
+    pub fn flags(&self) -> u16 {
+        self.flags
+    }
+
+    /// Payload protocol identifier, exactly as set by the sender (no byte order conversion)
+    #[inline]
+    pub fn ppid(&self) -> u32 {
+        self.ppid
+    }
+
+    /// Number of bytes of the next message already received
+    #[inline]
+    pub fn length(&self) -> u32 {
+        self.length
+    }
+
+    #[inline]
+    pub fn assoc_id(&self) -> AssocId {
+        self.assoc_id
+    }
+
+    #[inline]
+    pub fn unordered(&self) -> bool {
+        self.flags & sys::sctp_sinfo_flags::SCTP_UNORDERED as u16 != 0
+    }
+
+    #[inline]
+    pub fn is_notification(&self) -> bool {
+        self.flags as i32 & sys::MSG_NOTIFICATION != 0
+    }
+}
+
+impl From<sys::sctp_nxtinfo> for NxtInfo {
+    fn from(info: sys::sctp_nxtinfo) -> NxtInfo {
+        NxtInfo {
+            stream: info.nxt_sid,
+            flags: info.nxt_flags,
+            ppid: info.nxt_ppid,
+            length: info.nxt_length,
+            assoc_id: AssocId::from(info.nxt_assoc_id)
+        }
+    }
+}
+
+/// Partial reliability policy of a message (PR-SCTP, RFC 3758)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrPolicy {
+    /// Abandon the message once it has been queued for longer than this
+    Ttl(Duration),
+    /// Abandon the message after this many retransmissions
+    Rtx(u32),
+    /// Abandon the message in favour of higher priority ones (lower value) when the send buffer is full
+    Prio(u32)
+}
+
+impl PrPolicy {
+    pub fn to_raw(&self) -> sys::sctp_prinfo {
+        match *self {
+            PrPolicy::Ttl(ttl) => sys::sctp_prinfo { pr_policy: sys::SCTP_PR_SCTP_TTL, pr_value: millis(ttl, u32::MAX as u64) as u32 },
+            PrPolicy::Rtx(count) => sys::sctp_prinfo { pr_policy: sys::SCTP_PR_SCTP_RTX, pr_value: count },
+            PrPolicy::Prio(prio) => sys::sctp_prinfo { pr_policy: sys::SCTP_PR_SCTP_PRIO, pr_value: prio }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SendOptions {
+    stream: u16,
+    ppid: u32,
+    flags: u16,
+    ttl: u32,
+    context: u32,
+    pr_policy: Option<PrPolicy>,
+    auth_key: Option<u16>
+}
+
+impl SendOptions {
+    #[inline]
+    pub fn new() -> SendOptions {
+        SendOptions::default()
+    }
+
+    #[inline]
+    pub fn stream(mut self, stream: u16) -> SendOptions {
+        self.stream = stream;
+        self
+    }
+
+    /// Payload protocol identifier, passed to the peer as is (no byte order conversion)
+    #[inline]
+    pub fn ppid(mut self, ppid: u32) -> SendOptions {
+        self.ppid = ppid;
+        self
+    }
+
+    #[inline]
+    pub fn context(mut self, context: u32) -> SendOptions {
+        self.context = context;
+        self
+    }
+
+    /// Lifetime of the message for PR-SCTP, rounded down to milliseconds
+    #[inline]
+    pub fn ttl(mut self, ttl: Duration) -> SendOptions {
+        self.ttl = millis(ttl, u32::MAX as u64) as u32;
+        self
+    }
+
+    /// PR-SCTP policy, taking precedence over `ttl`
+    #[inline]
+    pub fn pr_policy(mut self, policy: PrPolicy) -> SendOptions {
+        self.pr_policy = Some(policy);
+        self
+    }
+
+    /// Shared key to authenticate the message with, instead of the active one
+    #[inline]
+    pub fn auth_key(mut self, keynumber: u16) -> SendOptions {
+        self.auth_key = Some(keynumber);
+        self
+    }
+
+    #[inline]
+    pub fn unordered(self, unordered: bool) -> SendOptions {
+        self.flag(sys::sctp_sinfo_flags::SCTP_UNORDERED, unordered)
+    }
+
+    #[inline]
+    pub fn sack_immediately(self, sack_immediately: bool) -> SendOptions {
+        self.flag(sys::sctp_sinfo_flags::SCTP_SACK_IMMEDIATELY, sack_immediately)
+    }
+
+    #[inline]
+    pub fn addr_over(self, addr_over: bool) -> SendOptions {
+        self.flag(sys::sctp_sinfo_flags::SCTP_ADDR_OVER, addr_over)
+    }
+
+    pub fn to_sndinfo(&self, assoc_id: sys::sctp_assoc_t) -> sys::sctp_sndinfo {
+        sys::sctp_sndinfo {
+            snd_sid: self.stream,
+            snd_flags: self.flags,
+            snd_ppid: self.ppid,
+            snd_context: self.context,
+            snd_assoc_id: assoc_id
+        }
+    }
+
+    pub fn to_prinfo(&self) -> Option<sys::sctp_prinfo> {
+        match self.pr_policy {
+            Some(policy) => Some(policy.to_raw()),
+            None if self.ttl != 0 => Some(sys::sctp_prinfo { pr_policy: sys::SCTP_PR_SCTP_TTL, pr_value: self.ttl }),
+            None => None
+        }
+    }
+
+    pub fn to_authinfo(&self) -> Option<sys::sctp_authinfo> {
+        self.auth_key.map(|keynumber| sys::sctp_authinfo { auth_keynumber: keynumber })
+    }
+
+    /// Whether the options go beyond what `sctp_sndrcvinfo` can carry
+    pub fn needs_sendv(&self) -> bool {
+        self.pr_policy.is_some() || self.auth_key.is_some()
+    }
+
+    fn flag(mut self, flag: sys::sctp_sinfo_flags, on: bool) -> SendOptions {
+        if on {
+            self.flags |= flag as u16;
+        } else {
+            self.flags &= !(flag as u16);
+        }
+        self
+    }
+}
+
+impl<'a> From<&'a SendOptions> for sys::sctp_sndrcvinfo {
+    fn from(options: &'a SendOptions) -> sys::sctp_sndrcvinfo {
+        let mut info: sys::sctp_sndrcvinfo = unsafe { mem::zeroed() };
+
+        info.sinfo_stream = options.stream;
+        info.sinfo_flags = options.flags;
+        info.sinfo_ppid = options.ppid;
+        info.sinfo_context = options.context;
+        info.sinfo_timetolive = options.ttl;
+
+        info
+    }
+}
@@ -0,0 +1,175 @@
+use std::cmp;
+use std::io;
+
+use libc;
+
+/// The buffer `recv_message` receives whole messages into. It grows as needed and remembers
+/// when a message was interrupted (by `WouldBlock` or another error), so the next call with
+/// the same buffer continues that message instead of starting a new one.
+///
+/// The rest of an interrupted message is only recognised by the buffer holding its start:
+/// keep one buffer per socket and pass it to every `recv_message` call, also through a
+/// `try_clone`d handle.
+#[derive(Debug, Clone, Default)]
+pub struct MessageBuf {
+    buf: Vec<u8>,
+    partial: bool
+}
+
+impl MessageBuf {
+    #[inline]
+    pub fn new() -> MessageBuf {
+        MessageBuf::default()
+    }
+
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> MessageBuf {
+        MessageBuf::from(Vec::with_capacity(capacity))
+    }
+
+    /// The last message received, or the part read so far while `is_partial`
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// A message was interrupted and the next `recv_message` continues it
+    #[inline]
+    pub fn is_partial(&self) -> bool {
+        self.partial
+    }
+
+    #[inline]
+    pub fn into_vec(self) -> Vec<u8> {
+        self.buf
+    }
+
+    /// Read the rest of the current message with `recv` until it reports `MSG_EOR` or end of
+    /// file, returning the message length along with the flags and `T` of the last read
+    #[doc(hidden)]
+    pub fn fill<T, F>(&mut self, mut recv: F) -> io::Result<(usize, libc::c_int, T)>
+        where F: FnMut(&mut [u8]) -> io::Result<(usize, libc::c_int, T)>
+    {
+        const CHUNK: usize = 4096;
+
+        if !self.partial {
+            self.buf.clear();
+        }
+
+        loop {
+            let offset = self.buf.len();
+            let chunk = cmp::max(CHUNK, offset);
+            self.buf.resize(offset + chunk, 0);
+
+            let (size, flags, last) = match recv(&mut self.buf[offset..]) {
+                Ok(ret) => ret,
+                Err(e) => {
+                    self.buf.truncate(offset);
+                    self.partial = offset != 0;
+                    return Err(e)
+                }
+            };
+
+            self.buf.truncate(offset + size);
+
+            if size == 0 || flags & libc::MSG_EOR == libc::MSG_EOR {
+                self.partial = false;
+                return Ok((self.buf.len(), flags, last))
+            }
+        }
+    }
+}
+
+/// Reuses the allocation of `buf`, its contents are dropped by the next `recv_message`
+impl From<Vec<u8>> for MessageBuf {
+    fn from(buf: Vec<u8>) -> MessageBuf {
+        MessageBuf {
+            buf,
+            partial: false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::io::{self, ErrorKind};
+
+    use libc;
+
+    use super::MessageBuf;
+
+    /// Hands out `reads` one per call, a `None` fails with `WouldBlock`
+    fn reader(reads: Vec<Option<(&'static [u8], bool)>>) -> impl FnMut(&mut [u8]) -> io::Result<(usize, libc::c_int, ())> {
+        let mut reads: VecDeque<_> = reads.into();
+
+        move |buf| match reads.pop_front().expect("read past the script") {
+            Some((data, eor)) => {
+                buf[..data.len()].copy_from_slice(data);
+                Ok((data.len(), if eor { libc::MSG_EOR } else { 0 }, ()))
+            }
+            None => Err(io::Error::from(ErrorKind::WouldBlock))
+        }
+    }
+
+    #[test]
+    fn joins_fragments_up_to_eor() {
+        let mut msg = MessageBuf::new();
+        let mut recv = reader(vec![Some((b"hel", false)), Some((b"lo", true))]);
+
+        let (size, flags, ()) = msg.fill(&mut recv).unwrap();
+
+        assert_eq!(size, 5);
+        assert_eq!(flags, libc::MSG_EOR);
+        assert_eq!(msg.as_slice(), b"hello");
+        assert!(!msg.is_partial());
+    }
+
+    #[test]
+    fn resumes_after_would_block() {
+        let mut msg = MessageBuf::new();
+        let mut recv = reader(vec![Some((b"hel", false)), None, Some((b"lo", true))]);
+
+        let err = msg.fill(&mut recv).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::WouldBlock);
+        assert!(msg.is_partial());
+        assert_eq!(msg.as_slice(), b"hel");
+
+        let (size, _, ()) = msg.fill(&mut recv).unwrap();
+        assert_eq!(size, 5);
+        assert_eq!(msg.as_slice(), b"hello");
+        assert!(!msg.is_partial());
+    }
+
+    #[test]
+    fn would_block_before_any_data_is_not_partial() {
+        let mut msg = MessageBuf::new();
+        let mut recv = reader(vec![None]);
+
+        msg.fill(&mut recv).unwrap_err();
+        assert!(!msg.is_partial());
+        assert!(msg.as_slice().is_empty());
+    }
+
+    #[test]
+    fn next_message_replaces_the_last() {
+        let mut msg = MessageBuf::from(b"stale".to_vec());
+        let mut recv = reader(vec![Some((b"one", true)), Some((b"two", true))]);
+
+        msg.fill(&mut recv).unwrap();
+        assert_eq!(msg.as_slice(), b"one");
+
+        msg.fill(&mut recv).unwrap();
+        assert_eq!(msg.as_slice(), b"two");
+    }
+
+    #[test]
+    fn stops_at_end_of_file() {
+        let mut msg = MessageBuf::new();
+        let mut recv = reader(vec![Some((b"", false))]);
+
+        let (size, _, ()) = msg.fill(&mut recv).unwrap();
+        assert_eq!(size, 0);
+        assert!(!msg.is_partial());
+    }
+}
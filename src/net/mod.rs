@@ -5,6 +5,11 @@ pub mod socket;
 pub mod addr;
 pub mod event;
 pub mod notification;
+pub mod assoc;
+pub mod info;
+pub mod message;
+pub mod opt;
+pub mod status;
 
 #[doc(hidden)]
 pub trait IsMinusOne {
@@ -1,34 +1,186 @@
-use std::mem;
-use std::net::SocketAddr;
+use std::error;
+use std::fmt;
+use std::net::{SocketAddr, SocketAddrV4, SocketAddrV6, Ipv4Addr, Ipv6Addr};
 use std::io;
+use std::time::Duration;
+
+use libc;
 
 use sys::*;
 
-use net::addr;
+use net::assoc::AssocId;
+use net::info::RecvInfo;
+
+// Offsets follow the kernel's struct layouts in <linux/sctp.h>, fields are in host byte order
+// unless read with `read_be16`
+const HEADER_LEN: usize = 8;
+const ASSOC_CHANGE_LEN: usize = 20;
+const PADDR_CHANGE_LEN: usize = 148;
+const REMOTE_ERROR_LEN: usize = 16;
+const SEND_FAILED_LEN: usize = 48;
+const SHUTDOWN_EVENT_LEN: usize = 12;
+const ADAPTATION_EVENT_LEN: usize = 16;
+const PDAPI_EVENT_LEN: usize = 16;
+const AUTHKEY_EVENT_LEN: usize = 20;
+const SENDER_DRY_EVENT_LEN: usize = 12;
+const STREAM_RESET_EVENT_LEN: usize = 12;
+const ASSOC_RESET_EVENT_LEN: usize = 20;
+const STREAM_CHANGE_EVENT_LEN: usize = 16;
+const SEND_FAILED_EVENT_LEN: usize = 32;
+const SOCKADDR_STORAGE_LEN: usize = 128;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotificationParseError {
+    /// The buffer is shorter than the notification claims or needs to be
+    Truncated { expected: usize, actual: usize },
+    /// The length in the header is too short for the notification type
+    InvalidLength { sn_type: u16, length: usize },
+    /// The address in a notification has an unknown family
+    UnsupportedFamily(u16)
+}
 
-pub fn notification_parse(buf: &[u8]) -> io::Result<Option<Notification>> {
+impl fmt::Display for NotificationParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            NotificationParseError::Truncated { expected, actual } => {
+                write!(f, "Truncated notification: expected {} bytes, got {}", expected, actual)
+            }
+            NotificationParseError::InvalidLength { sn_type, length } => {
+                write!(f, "Invalid length {} for notification type {}", length, sn_type)
+            }
+            NotificationParseError::UnsupportedFamily(family) => {
+                write!(f, "Unsupported address family : {}", family)
+            }
+        }
+    }
+}
 
-    let notification: &sctp_notification = unsafe { mem::transmute(buf.as_ptr()) };
+impl error::Error for NotificationParseError {}
 
-    let header = unsafe { notification.sn_header };
+impl From<NotificationParseError> for io::Error {
+    fn from(err: NotificationParseError) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, err)
+    }
+}
 
-    if header.sn_length as usize != buf.len() {
-        return Ok(None)
+fn read_u16(buf: &[u8], offset: usize) -> Result<u16, NotificationParseError> {
+    match buf.get(offset..offset + 2) {
+        Some(b) => Ok(u16::from_ne_bytes([b[0], b[1]])),
+        None => Err(NotificationParseError::Truncated { expected: offset + 2, actual: buf.len() })
     }
+}
+
+fn read_be16(buf: &[u8], offset: usize) -> Result<u16, NotificationParseError> {
+    read_u16(buf, offset).map(u16::from_be)
+}
+
+fn read_u32(buf: &[u8], offset: usize) -> Result<u32, NotificationParseError> {
+    match buf.get(offset..offset + 4) {
+        Some(b) => Ok(u32::from_ne_bytes([b[0], b[1], b[2], b[3]])),
+        None => Err(NotificationParseError::Truncated { expected: offset + 4, actual: buf.len() })
+    }
+}
+
+fn read_i32(buf: &[u8], offset: usize) -> Result<i32, NotificationParseError> {
+    read_u32(buf, offset).map(|v| v as i32)
+}
+
+fn read_bytes(buf: &[u8], offset: usize, len: usize) -> Result<&[u8], NotificationParseError> {
+    match buf.get(offset..offset + len) {
+        Some(b) => Ok(b),
+        None => Err(NotificationParseError::Truncated { expected: offset + len, actual: buf.len() })
+    }
+}
+
+fn read_assoc_id(buf: &[u8], offset: usize) -> Result<AssocId, NotificationParseError> {
+    read_i32(buf, offset).map(AssocId::from)
+}
+
+fn read_addr(buf: &[u8], offset: usize) -> Result<SocketAddr, NotificationParseError> {
+    let family = read_u16(buf, offset)?;
 
-    let sn_type = match sctp_sn_type::from_u16(header.sn_type) {
+    match family as libc::c_int {
+        libc::AF_INET => {
+            let b = read_bytes(buf, offset, 8)?;
+            let port = u16::from_be_bytes([b[2], b[3]]);
+            let ip = Ipv4Addr::new(b[4], b[5], b[6], b[7]);
+
+            Ok(SocketAddr::V4(SocketAddrV4::new(ip, port)))
+        }
+        libc::AF_INET6 => {
+            let b = read_bytes(buf, offset, 28)?;
+            let port = u16::from_be_bytes([b[2], b[3]]);
+            let flowinfo = u32::from_ne_bytes([b[4], b[5], b[6], b[7]]);
+            let mut octets = [0u8; 16];
+            octets.copy_from_slice(&b[8..24]);
+            let scope_id = u32::from_ne_bytes([b[24], b[25], b[26], b[27]]);
+
+            Ok(SocketAddr::V6(SocketAddrV6::new(Ipv6Addr::from(octets), port, flowinfo, scope_id)))
+        }
+        _ => Err(NotificationParseError::UnsupportedFamily(family))
+    }
+}
+
+fn read_sndrcvinfo(buf: &[u8], offset: usize) -> Result<RecvInfo, NotificationParseError> {
+    let info = sctp_sndrcvinfo {
+        sinfo_stream: read_u16(buf, offset)?,
+        sinfo_ssn: read_u16(buf, offset + 2)?,
+        sinfo_flags: read_u16(buf, offset + 4)?,
+        sinfo_ppid: read_u32(buf, offset + 8)?,
+        sinfo_context: read_u32(buf, offset + 12)?,
+        sinfo_timetolive: read_u32(buf, offset + 16)?,
+        sinfo_tsn: read_u32(buf, offset + 20)?,
+        sinfo_cumtsn: read_u32(buf, offset + 24)?,
+        sinfo_assoc_id: read_i32(buf, offset + 28)?
+    };
+
+    Ok(RecvInfo::from(info))
+}
+
+/// Parse a notification read with `MSG_NOTIFICATION` set, unknown notification types yield
+/// `Ok(None)`
+pub fn notification_parse(buf: &[u8]) -> Result<Option<Notification>, NotificationParseError> {
+    let raw_type = read_u16(buf, 0)?;
+    let length = read_u32(buf, 4)? as usize;
+
+    if length > buf.len() {
+        return Err(NotificationParseError::Truncated { expected: length, actual: buf.len() })
+    }
+
+    let buf = &buf[..length];
+
+    let sn_type = match sctp_sn_type::from_u16(raw_type) {
         Some(sn_type) => sn_type,
         None => return Ok(None)
     };
 
+    let min_length = match sn_type {
+        sctp_sn_type::SCTP_SN_TYPE_BASE => HEADER_LEN,
+        sctp_sn_type::SCTP_ASSOC_CHANGE => ASSOC_CHANGE_LEN,
+        sctp_sn_type::SCTP_PEER_ADDR_CHANGE => PADDR_CHANGE_LEN,
+        sctp_sn_type::SCTP_REMOTE_ERROR => REMOTE_ERROR_LEN,
+        sctp_sn_type::SCTP_SEND_FAILED => SEND_FAILED_LEN,
+        sctp_sn_type::SCTP_SHUTDOWN_EVENT => SHUTDOWN_EVENT_LEN,
+        sctp_sn_type::SCTP_PARTIAL_DELIVERY_EVENT => PDAPI_EVENT_LEN,
+        sctp_sn_type::SCTP_ADAPTATION_INDICATION => ADAPTATION_EVENT_LEN,
+        sctp_sn_type::SCTP_AUTHENTICATION_INDICATION => AUTHKEY_EVENT_LEN,
+        sctp_sn_type::SCTP_SENDER_DRY_EVENT => SENDER_DRY_EVENT_LEN,
+        sctp_sn_type::SCTP_STREAM_RESET_EVENT => STREAM_RESET_EVENT_LEN,
+        sctp_sn_type::SCTP_ASSOC_RESET_EVENT => ASSOC_RESET_EVENT_LEN,
+        sctp_sn_type::SCTP_STREAM_CHANGE_EVENT => STREAM_CHANGE_EVENT_LEN,
+        sctp_sn_type::SCTP_SEND_FAILED_EVENT => SEND_FAILED_EVENT_LEN
+    };
+
+    if length < min_length {
+        return Err(NotificationParseError::InvalidLength { sn_type: raw_type, length })
+    }
+
     match sn_type {
         sctp_sn_type::SCTP_SN_TYPE_BASE => {
-            return Ok(None)
+            Ok(None)
         }
         sctp_sn_type::SCTP_ASSOC_CHANGE => {
-            let n = unsafe { notification.sn_assoc_change };
-
-            let state = match n.sac_state {
+            let state = match read_u16(buf, 8)? {
                 0 => AssocChangeState::CommUp,
                 1 => AssocChangeState::CommLost,
                 2 => AssocChangeState::Restart,
@@ -37,141 +189,175 @@ pub fn notification_parse(buf: &[u8]) -> io::Result<Option<Notification>> {
                 _ => AssocChangeState::Unkown
             };
 
-            let error = notification_error_parse(n.sac_error);
-
-            let data_length = n.sac_length as usize - mem::size_of::<u16>() * 6 - mem::size_of::<u32>() - mem::size_of::<i32>();
-
-            let data: Vec<u8> = unsafe { n.sac_info.as_slice(data_length).to_vec() };
-
             let assoc_change = AssocChange {
-                state: state,
-                error: error,
-                outbound_streams: n.sac_outbound_streams,
-                inbound_streams: n.sac_inbound_streams,
-                assoc_id: n.sac_assoc_id,
-                data: data
+                state,
+                error: read_u16(buf, 10)?,
+                outbound_streams: read_u16(buf, 12)?,
+                inbound_streams: read_u16(buf, 14)?,
+                assoc_id: read_assoc_id(buf, 16)?,
+                data: buf[ASSOC_CHANGE_LEN..].to_vec()
             };
 
-            return Ok(Some(Notification::AssocChange(assoc_change)))
-
+            Ok(Some(Notification::AssocChange(assoc_change)))
         }
         sctp_sn_type::SCTP_PEER_ADDR_CHANGE => {
-            let n = unsafe { notification.sn_paddr_change };
+            let addr = read_addr(buf, 8)?;
 
-            let addr = addr::sockaddr_to_addr(&n.spc_addr, 128)?;
+            let offset = 8 + SOCKADDR_STORAGE_LEN;
 
-            let state = match n.spc_state {
+            let state = match read_i32(buf, offset)? {
                 0 => PaddrChangeState::AddrAvaliable,
                 1 => PaddrChangeState::AddrUnreachable,
                 2 => PaddrChangeState::AddrRemove,
                 3 => PaddrChangeState::AddrAdded,
                 4 => PaddrChangeState::AddrMadePrim,
                 5 => PaddrChangeState::AddrConfirmed,
+                6 => PaddrChangeState::AddrPotentiallyFailed,
                 _ => PaddrChangeState::Unkown
             };
 
-            let error = notification_error_parse(n.spc_error as u16);
+            let error = read_i32(buf, offset + 4)?;
 
             let paddr_change = PaddrChange {
-                addr: addr,
-                state: state,
-                error: error,
-                assoc_id: n.spc_assoc_id
+                addr,
+                state,
+                error,
+                assoc_id: read_assoc_id(buf, offset + 8)?
             };
 
-            return Ok(Some(Notification::PaddrChange(paddr_change)))
+            Ok(Some(Notification::PaddrChange(paddr_change)))
         }
         sctp_sn_type::SCTP_REMOTE_ERROR => {
-            let n = unsafe { notification.sn_remote_error };
-
-            let error = notification_error_parse(n.sre_error);
-
-            let data_length = n.sre_length as usize - mem::size_of::<u16>() * 3 - mem::size_of::<u32>() - mem::size_of::<i32>();
-
-            let data: Vec<u8> = unsafe { n.sre_data.as_slice(data_length).to_vec() };
-
             let remote_error = RemoteError {
-                error: error,
-                assoc_id: n.sre_assoc_id,
-                data: data
+                error: read_be16(buf, 8)?,
+                assoc_id: read_assoc_id(buf, 12)?,
+                data: buf[REMOTE_ERROR_LEN..].to_vec()
             };
 
-            return Ok(Some(Notification::RemoteError(remote_error)))
+            Ok(Some(Notification::RemoteError(remote_error)))
         }
         sctp_sn_type::SCTP_SEND_FAILED => {
-            let n = unsafe { notification.sn_send_failed };
-
-            let state = match n.ssf_flags {
+            let state = match read_u16(buf, 2)? {
                 0 => SendFailedState::DataUnsent,
                 1 => SendFailedState::DataSent,
                 _ => SendFailedState::Unkown
             };
 
-            let error = notification_error_parse(n.ssf_error as u16);
-
-            let data_length = n.ssf_length as usize - mem::size_of::<u16>() * 2 - mem::size_of::<u32>() * 2 - mem::size_of::<sctp_sndrcvinfo>() - mem::size_of::<i32>();
-
-            let data: Vec<u8> = unsafe { n.ssf_data.as_slice(data_length).to_vec() };
-
             let send_failed = SendFailed {
-                state: state,
-                error: error,
-                info: n.ssf_info,
-                assoc_id: n.ssf_assoc_id,
-                data: data
+                state,
+                error: read_u32(buf, 8)?,
+                info: read_sndrcvinfo(buf, 12)?,
+                ttl: Duration::from_millis(read_u32(buf, 28)? as u64),
+                assoc_id: read_assoc_id(buf, 44)?,
+                data: buf[SEND_FAILED_LEN..].to_vec()
             };
 
-            return Ok(Some(Notification::SendFailed(send_failed)))
+            Ok(Some(Notification::SendFailed(send_failed)))
         }
         sctp_sn_type::SCTP_SHUTDOWN_EVENT => {
-            let n = unsafe { notification.sn_shutdown_event };
-
             let shutdown = Shutdown {
-                assoc_id: n.sse_assoc_id
+                assoc_id: read_assoc_id(buf, 8)?
             };
 
-            return Ok(Some(Notification::Shutdown(shutdown)))
+            Ok(Some(Notification::Shutdown(shutdown)))
         }
         sctp_sn_type::SCTP_PARTIAL_DELIVERY_EVENT => {
-            let n = unsafe { notification.sn_pdapi_event };
+            // Older kernels end the event before the stream and sequence number
+            let (stream, seq) = if buf.len() >= PDAPI_EVENT_LEN + 8 {
+                (Some(read_u32(buf, 16)?), Some(read_u32(buf, 20)?))
+            } else {
+                (None, None)
+            };
 
             let partial_delivery = PartialDelivery {
-                indication: n.pdapi_indication,
-                assoc_id: n.pdapi_assoc_id
+                indication: PartialDeliveryIndication::from(read_u32(buf, 8)?),
+                assoc_id: read_assoc_id(buf, 12)?,
+                stream,
+                seq
             };
 
-            return Ok(Some(Notification::PartialDelivery(partial_delivery)))
+            Ok(Some(Notification::PartialDelivery(partial_delivery)))
         }
         sctp_sn_type::SCTP_ADAPTATION_INDICATION => {
-            let n = unsafe { notification.sn_adaptation_event };
-
             let adaptation = Adaptation {
-                adaptation_ind: n.sai_adaptation_ind,
-                assoc_id: n.sai_assoc_id
+                adaptation_ind: read_u32(buf, 8)?,
+                assoc_id: read_assoc_id(buf, 12)?
             };
 
-            return Ok(Some(Notification::Adaptation(adaptation)))
+            Ok(Some(Notification::Adaptation(adaptation)))
         }
         sctp_sn_type::SCTP_AUTHENTICATION_INDICATION => {
-            let n = unsafe { notification.sn_authkey_event };
-
             let authkey = Authkey {
-                keynumber: n.auth_keynumber,
-                altkeynumber: n.auth_altkeynumber,
-                indication: n.auth_indication,
-                assoc_id: n.auth_assoc_id
+                keynumber: read_u16(buf, 8)?,
+                altkeynumber: read_u16(buf, 10)?,
+                indication: AuthIndication::from(read_u32(buf, 12)?),
+                assoc_id: read_assoc_id(buf, 16)?
             };
 
-            return Ok(Some(Notification::Authkey(authkey)))
+            Ok(Some(Notification::Authkey(authkey)))
         }
         sctp_sn_type::SCTP_SENDER_DRY_EVENT => {
-            let n = unsafe { notification.sn_sender_dry_event };
-
             let sender_dry = SenderDry {
-                assoc_id: n.sender_dry_assoc_id
+                assoc_id: read_assoc_id(buf, 8)?
             };
 
-            return Ok(Some(Notification::SenderDry(sender_dry)))
+            Ok(Some(Notification::SenderDry(sender_dry)))
+        }
+        sctp_sn_type::SCTP_STREAM_RESET_EVENT => {
+            let streams = buf[STREAM_RESET_EVENT_LEN..]
+                .chunks_exact(2)
+                .map(|b| u16::from_ne_bytes([b[0], b[1]]))
+                .collect();
+
+            let stream_reset = StreamReset {
+                flags: read_u16(buf, 2)?,
+                assoc_id: read_assoc_id(buf, 8)?,
+                streams
+            };
+
+            Ok(Some(Notification::StreamReset(stream_reset)))
+        }
+        sctp_sn_type::SCTP_ASSOC_RESET_EVENT => {
+            let assoc_reset = AssocReset {
+                flags: read_u16(buf, 2)?,
+                assoc_id: read_assoc_id(buf, 8)?,
+                local_tsn: read_u32(buf, 12)?,
+                remote_tsn: read_u32(buf, 16)?
+            };
+
+            Ok(Some(Notification::AssocReset(assoc_reset)))
+        }
+        sctp_sn_type::SCTP_STREAM_CHANGE_EVENT => {
+            let stream_change = StreamChange {
+                flags: read_u16(buf, 2)?,
+                assoc_id: read_assoc_id(buf, 8)?,
+                inbound_streams: read_u16(buf, 12)?,
+                outbound_streams: read_u16(buf, 14)?
+            };
+
+            Ok(Some(Notification::StreamChange(stream_change)))
+        }
+        sctp_sn_type::SCTP_SEND_FAILED_EVENT => {
+            let state = match read_u16(buf, 2)? {
+                0 => SendFailedState::DataUnsent,
+                1 => SendFailedState::DataSent,
+                _ => SendFailedState::Unkown
+            };
+
+            let error = read_u32(buf, 8)?;
+
+            let send_failed = SendFailedEvent {
+                state,
+                error,
+                stream: read_u16(buf, 12)?,
+                flags: read_u16(buf, 14)?,
+                ppid: read_u32(buf, 16)?,
+                context: read_u32(buf, 20)?,
+                assoc_id: read_assoc_id(buf, 28)?,
+                data: buf[SEND_FAILED_EVENT_LEN..].to_vec()
+            };
+
+            Ok(Some(Notification::SendFailedEvent(send_failed)))
         }
     }
 }
@@ -231,6 +417,22 @@ impl sctp_sn_type {
             return Some(sctp_sn_type::SCTP_SENDER_DRY_EVENT)
         }
 
+        if sctp_sn_type::SCTP_STREAM_RESET_EVENT as u16 == t {
+            return Some(sctp_sn_type::SCTP_STREAM_RESET_EVENT)
+        }
+
+        if sctp_sn_type::SCTP_ASSOC_RESET_EVENT as u16 == t {
+            return Some(sctp_sn_type::SCTP_ASSOC_RESET_EVENT)
+        }
+
+        if sctp_sn_type::SCTP_STREAM_CHANGE_EVENT as u16 == t {
+            return Some(sctp_sn_type::SCTP_STREAM_CHANGE_EVENT)
+        }
+
+        if sctp_sn_type::SCTP_SEND_FAILED_EVENT as u16 == t {
+            return Some(sctp_sn_type::SCTP_SEND_FAILED_EVENT)
+        }
+
         None
     }
 }
@@ -245,11 +447,35 @@ pub enum Notification {
     Adaptation(Adaptation),
     PartialDelivery(PartialDelivery),
     Authkey(Authkey),
-    SenderDry(SenderDry)
+    SenderDry(SenderDry),
+    StreamReset(StreamReset),
+    AssocReset(AssocReset),
+    StreamChange(StreamChange),
+    SendFailedEvent(SendFailedEvent)
 }
 
+impl Notification {
+    pub fn assoc_id(&self) -> AssocId {
+        match *self {
+            Notification::AssocChange(ref n) => n.assoc_id(),
+            Notification::PaddrChange(ref n) => n.assoc_id(),
+            Notification::RemoteError(ref n) => n.assoc_id(),
+            Notification::SendFailed(ref n) => n.assoc_id(),
+            Notification::Shutdown(ref n) => n.assoc_id(),
+            Notification::Adaptation(ref n) => n.assoc_id(),
+            Notification::PartialDelivery(ref n) => n.assoc_id(),
+            Notification::Authkey(ref n) => n.assoc_id(),
+            Notification::SenderDry(ref n) => n.assoc_id(),
+            Notification::StreamReset(ref n) => n.assoc_id(),
+            Notification::AssocReset(ref n) => n.assoc_id(),
+            Notification::StreamChange(ref n) => n.assoc_id(),
+            Notification::SendFailedEvent(ref n) => n.assoc_id()
+        }
+    }
+}
 
-#[derive(Debug, Clone)]
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NotificationError {
     FailedThreshold,
     ReceivedSack,
@@ -264,14 +490,48 @@ pub enum NotificationError {
 #[derive(Debug, Clone)]
 pub struct AssocChange {
     state: AssocChangeState,
-    error: NotificationError,
+    error: u16,
     outbound_streams: u16,
     inbound_streams: u16,
-    assoc_id: i32,
+    assoc_id: AssocId,
     data: Vec<u8>
 }
 
-#[derive(Debug, Clone)]
+impl AssocChange {
+    #[inline]
+    pub fn state(&self) -> AssocChangeState {
+        self.state
+    }
+
+    /// The error that caused the change, as reported by the kernel
+    #[inline]
+    pub fn error(&self) -> u16 {
+        self.error
+    }
+
+    #[inline]
+    pub fn outbound_streams(&self) -> u16 {
+        self.outbound_streams
+    }
+
+    #[inline]
+    pub fn inbound_streams(&self) -> u16 {
+        self.inbound_streams
+    }
+
+    #[inline]
+    pub fn assoc_id(&self) -> AssocId {
+        self.assoc_id
+    }
+
+    /// The ABORT cause or the supported features, depending on the state
+    #[inline]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AssocChangeState {
     CommUp,
     CommLost,
@@ -285,11 +545,34 @@ pub enum AssocChangeState {
 pub struct PaddrChange {
     addr: SocketAddr,
     state: PaddrChangeState,
-    error: NotificationError,
-    assoc_id: i32,
+    error: i32,
+    assoc_id: AssocId,
 }
 
-#[derive(Debug, Clone)]
+impl PaddrChange {
+    #[inline]
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    #[inline]
+    pub fn state(&self) -> PaddrChangeState {
+        self.state
+    }
+
+    /// The error code for the state change, as reported by the kernel
+    #[inline]
+    pub fn error(&self) -> i32 {
+        self.error
+    }
+
+    #[inline]
+    pub fn assoc_id(&self) -> AssocId {
+        self.assoc_id
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PaddrChangeState {
     AddrAvaliable,
     AddrUnreachable,
@@ -297,26 +580,85 @@ pub enum PaddrChangeState {
     AddrAdded,
     AddrMadePrim,
     AddrConfirmed,
+    /// The path crossed its potentially-failed threshold (RFC 7829), data moves to other paths
+    /// until it answers again
+    AddrPotentiallyFailed,
     Unkown
 }
 
 #[derive(Debug, Clone)]
 pub struct RemoteError {
-    error: NotificationError,
-    assoc_id: i32,
+    error: u16,
+    assoc_id: AssocId,
     data: Vec<u8>
 }
 
+impl RemoteError {
+    /// The cause code of the peer's first error cause (RFC 9260 section 3.3.10)
+    #[inline]
+    pub fn error(&self) -> u16 {
+        self.error
+    }
+
+    #[inline]
+    pub fn assoc_id(&self) -> AssocId {
+        self.assoc_id
+    }
+
+    /// The error causes sent by the peer
+    #[inline]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SendFailed {
     state: SendFailedState,
-    error: NotificationError,
-    info: sctp_sndrcvinfo,
-    assoc_id: i32,
+    error: u32,
+    info: RecvInfo,
+    ttl: Duration,
+    assoc_id: AssocId,
     data: Vec<u8>
 }
 
-#[derive(Debug, Clone)]
+impl SendFailed {
+    #[inline]
+    pub fn state(&self) -> SendFailedState {
+        self.state
+    }
+
+    /// The error that made the send fail, as reported by the kernel
+    #[inline]
+    pub fn error(&self) -> u32 {
+        self.error
+    }
+
+    /// The send parameters of the undelivered message
+    #[inline]
+    pub fn info(&self) -> RecvInfo {
+        self.info
+    }
+
+    /// The time to live the undelivered message was sent with, zero for none
+    #[inline]
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    #[inline]
+    pub fn assoc_id(&self) -> AssocId {
+        self.assoc_id
+    }
+
+    /// The undelivered message
+    #[inline]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SendFailedState {
     DataUnsent,
     DataSent,
@@ -325,30 +667,743 @@ pub enum SendFailedState {
 
 #[derive(Debug, Clone)]
 pub struct Shutdown {
-    assoc_id: i32
+    assoc_id: AssocId
+}
+
+impl Shutdown {
+    #[inline]
+    pub fn assoc_id(&self) -> AssocId {
+        self.assoc_id
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct Adaptation {
     adaptation_ind: u32,
-    assoc_id: i32
+    assoc_id: AssocId
+}
+
+impl Adaptation {
+    #[inline]
+    pub fn adaptation_ind(&self) -> u32 {
+        self.adaptation_ind
+    }
+
+    #[inline]
+    pub fn assoc_id(&self) -> AssocId {
+        self.assoc_id
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct PartialDelivery {
-    indication: u32,
-    assoc_id: i32
+    indication: PartialDeliveryIndication,
+    assoc_id: AssocId,
+    stream: Option<u32>,
+    seq: Option<u32>
+}
+
+impl PartialDelivery {
+    #[inline]
+    pub fn indication(&self) -> PartialDeliveryIndication {
+        self.indication
+    }
+
+    #[inline]
+    pub fn assoc_id(&self) -> AssocId {
+        self.assoc_id
+    }
+
+    /// The stream whose partial delivery is reported, `None` on kernels that don't report it
+    #[inline]
+    pub fn stream(&self) -> Option<u32> {
+        self.stream
+    }
+
+    /// The stream sequence number of the partially delivered message, `None` on kernels that
+    /// don't report it
+    #[inline]
+    pub fn seq(&self) -> Option<u32> {
+        self.seq
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartialDeliveryIndication {
+    Aborted,
+    Unknown(u32)
+}
+
+impl From<u32> for PartialDeliveryIndication {
+    fn from(indication: u32) -> PartialDeliveryIndication {
+        match indication {
+            SCTP_PARTIAL_DELIVERY_ABORTED => PartialDeliveryIndication::Aborted,
+            _ => PartialDeliveryIndication::Unknown(indication)
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct Authkey {
     keynumber: u16,
     altkeynumber: u16,
-    indication: u32,
-    assoc_id: i32
+    indication: AuthIndication,
+    assoc_id: AssocId
+}
+
+impl Authkey {
+    #[inline]
+    pub fn keynumber(&self) -> u16 {
+        self.keynumber
+    }
+
+    #[inline]
+    pub fn altkeynumber(&self) -> u16 {
+        self.altkeynumber
+    }
+
+    #[inline]
+    pub fn indication(&self) -> AuthIndication {
+        self.indication
+    }
+
+    #[inline]
+    pub fn assoc_id(&self) -> AssocId {
+        self.assoc_id
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthIndication {
+    NewKey,
+    FreeKey,
+    NoAuth,
+    Unknown(u32)
+}
+
+impl From<u32> for AuthIndication {
+    fn from(indication: u32) -> AuthIndication {
+        match indication {
+            SCTP_AUTH_NEWKEY => AuthIndication::NewKey,
+            SCTP_AUTH_FREE_KEY => AuthIndication::FreeKey,
+            SCTP_AUTH_NO_AUTH => AuthIndication::NoAuth,
+            _ => AuthIndication::Unknown(indication)
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct SenderDry {
-    assoc_id: i32
+    assoc_id: AssocId
+}
+
+impl SenderDry {
+    #[inline]
+    pub fn assoc_id(&self) -> AssocId {
+        self.assoc_id
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StreamReset {
+    flags: u16,
+    assoc_id: AssocId,
+    streams: Vec<u16>
+}
+
+impl StreamReset {
+    /// The incoming streams were reset
+    #[inline]
+    pub fn incoming(&self) -> bool {
+        self.flags & SCTP_STREAM_RESET_INCOMING_SSN != 0
+    }
+
+    /// The outgoing streams were reset
+    #[inline]
+    pub fn outgoing(&self) -> bool {
+        self.flags & SCTP_STREAM_RESET_OUTGOING_SSN != 0
+    }
+
+    #[inline]
+    pub fn denied(&self) -> bool {
+        self.flags & SCTP_STREAM_RESET_DENIED != 0
+    }
+
+    #[inline]
+    pub fn failed(&self) -> bool {
+        self.flags & SCTP_STREAM_RESET_FAILED != 0
+    }
+
+    #[inline]
+    pub fn assoc_id(&self) -> AssocId {
+        self.assoc_id
+    }
+
+    /// The affected streams, empty when all of them were reset
+    #[inline]
+    pub fn streams(&self) -> &[u16] {
+        &self.streams
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AssocReset {
+    flags: u16,
+    assoc_id: AssocId,
+    local_tsn: u32,
+    remote_tsn: u32
+}
+
+impl AssocReset {
+    #[inline]
+    pub fn denied(&self) -> bool {
+        self.flags & SCTP_ASSOC_RESET_DENIED != 0
+    }
+
+    #[inline]
+    pub fn failed(&self) -> bool {
+        self.flags & SCTP_ASSOC_RESET_FAILED != 0
+    }
+
+    #[inline]
+    pub fn assoc_id(&self) -> AssocId {
+        self.assoc_id
+    }
+
+    /// The next TSN sent on the association
+    #[inline]
+    pub fn local_tsn(&self) -> u32 {
+        self.local_tsn
+    }
+
+    /// The next TSN expected from the peer
+    #[inline]
+    pub fn remote_tsn(&self) -> u32 {
+        self.remote_tsn
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StreamChange {
+    flags: u16,
+    assoc_id: AssocId,
+    inbound_streams: u16,
+    outbound_streams: u16
+}
+
+impl StreamChange {
+    #[inline]
+    pub fn denied(&self) -> bool {
+        self.flags & SCTP_STREAM_CHANGE_DENIED != 0
+    }
+
+    #[inline]
+    pub fn failed(&self) -> bool {
+        self.flags & SCTP_STREAM_CHANGE_FAILED != 0
+    }
+
+    #[inline]
+    pub fn assoc_id(&self) -> AssocId {
+        self.assoc_id
+    }
+
+    #[inline]
+    pub fn inbound_streams(&self) -> u16 {
+        self.inbound_streams
+    }
+
+    #[inline]
+    pub fn outbound_streams(&self) -> u16 {
+        self.outbound_streams
+    }
+}
+
+/// The `SCTP_SEND_FAILED_EVENT` counterpart of `SendFailed`
+#[derive(Debug, Clone)]
+pub struct SendFailedEvent {
+    state: SendFailedState,
+    error: u32,
+    stream: u16,
+    flags: u16,
+    ppid: u32,
+    context: u32,
+    assoc_id: AssocId,
+    data: Vec<u8>
+}
+
+impl SendFailedEvent {
+    #[inline]
+    pub fn state(&self) -> SendFailedState {
+        self.state
+    }
+
+    /// The error that made the send fail, as reported by the kernel
+    #[inline]
+    pub fn error(&self) -> u32 {
+        self.error
+    }
+
+    /// The stream the undelivered message was sent on
+    #[inline]
+    pub fn stream(&self) -> u16 {
+        self.stream
+    }
+
+    /// The send flags of the undelivered message
+    #[inline]
+    pub fn flags(&self) -> u16 {
+        self.flags
+    }
+
+    #[inline]
+    pub fn ppid(&self) -> u32 {
+        self.ppid
+    }
+
+    #[inline]
+    pub fn context(&self) -> u32 {
+        self.context
+    }
+
+    #[inline]
+    pub fn assoc_id(&self) -> AssocId {
+        self.assoc_id
+    }
+
+    /// The undelivered message
+    #[inline]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Notification body builder, fields in host byte order
+    struct Body(Vec<u8>);
+
+    impl Body {
+        fn new() -> Body {
+            Body(Vec::new())
+        }
+
+        fn u16(mut self, v: u16) -> Body {
+            self.0.extend_from_slice(&v.to_ne_bytes());
+            self
+        }
+
+        fn u32(mut self, v: u32) -> Body {
+            self.0.extend_from_slice(&v.to_ne_bytes());
+            self
+        }
+
+        fn i32(self, v: i32) -> Body {
+            self.u32(v as u32)
+        }
+
+        fn bytes(mut self, b: &[u8]) -> Body {
+            self.0.extend_from_slice(b);
+            self
+        }
+
+        fn zeros(mut self, len: usize) -> Body {
+            self.0.resize(self.0.len() + len, 0);
+            self
+        }
+
+        /// Prefix the header, the length covering the whole notification
+        fn notification(self, sn_type: u16, flags: u16) -> Vec<u8> {
+            Body::new()
+                .u16(sn_type)
+                .u16(flags)
+                .u32((HEADER_LEN + self.0.len()) as u32)
+                .bytes(&self.0)
+                .0
+        }
+    }
+
+    fn parse(buf: &[u8]) -> Notification {
+        notification_parse(buf).expect("parse failed").expect("unknown notification")
+    }
+
+    #[test]
+    fn assoc_change() {
+        let buf = Body::new()
+            .u16(2)
+            .u16(7)
+            .u16(10)
+            .u16(5)
+            .i32(3)
+            .bytes(b"features")
+            .notification(sctp_sn_type::SCTP_ASSOC_CHANGE as u16, 0);
+
+        match parse(&buf) {
+            Notification::AssocChange(n) => {
+                assert_eq!(n.state(), AssocChangeState::Restart);
+                assert_eq!(n.error(), 7);
+                assert_eq!(n.outbound_streams(), 10);
+                assert_eq!(n.inbound_streams(), 5);
+                assert_eq!(n.assoc_id(), AssocId::from(3));
+                assert_eq!(n.data(), b"features");
+            }
+            n => panic!("unexpected {:?}", n)
+        }
+    }
+
+    #[test]
+    fn paddr_change_v4() {
+        let buf = Body::new()
+            .u16(libc::AF_INET as u16)
+            .bytes(&[0x1f, 0x90, 127, 0, 0, 2])
+            .zeros(SOCKADDR_STORAGE_LEN - 8)
+            .i32(3)
+            .i32(0)
+            .i32(4)
+            .notification(sctp_sn_type::SCTP_PEER_ADDR_CHANGE as u16, 0);
+
+        match parse(&buf) {
+            Notification::PaddrChange(n) => {
+                assert_eq!(n.addr(), "127.0.0.2:8080".parse().unwrap());
+                assert_eq!(n.state(), PaddrChangeState::AddrAdded);
+                assert_eq!(n.error(), 0);
+                assert_eq!(n.assoc_id(), AssocId::from(4));
+            }
+            n => panic!("unexpected {:?}", n)
+        }
+    }
+
+    #[test]
+    fn paddr_change_v6() {
+        let mut ip = [0u8; 16];
+        ip[15] = 1;
+
+        let buf = Body::new()
+            .u16(libc::AF_INET6 as u16)
+            .bytes(&[0x1f, 0x90])
+            .u32(0)
+            .bytes(&ip)
+            .u32(2)
+            .zeros(SOCKADDR_STORAGE_LEN - 28)
+            .i32(1)
+            .i32(6)
+            .i32(4)
+            .notification(sctp_sn_type::SCTP_PEER_ADDR_CHANGE as u16, 0);
+
+        match parse(&buf) {
+            Notification::PaddrChange(n) => {
+                assert_eq!(n.addr(), SocketAddr::V6(SocketAddrV6::new(Ipv6Addr::from(ip), 8080, 0, 2)));
+                assert_eq!(n.state(), PaddrChangeState::AddrUnreachable);
+                assert_eq!(n.error(), 6);
+            }
+            n => panic!("unexpected {:?}", n)
+        }
+    }
+
+    #[test]
+    fn paddr_change_potentially_failed() {
+        let buf = Body::new()
+            .u16(libc::AF_INET as u16)
+            .bytes(&[0x1f, 0x90, 127, 0, 0, 3])
+            .zeros(SOCKADDR_STORAGE_LEN - 8)
+            .i32(6)
+            .i32(0)
+            .i32(4)
+            .notification(sctp_sn_type::SCTP_PEER_ADDR_CHANGE as u16, 0);
+
+        match parse(&buf) {
+            Notification::PaddrChange(n) => assert_eq!(n.state(), PaddrChangeState::AddrPotentiallyFailed),
+            n => panic!("unexpected {:?}", n)
+        }
+    }
+
+    #[test]
+    fn paddr_change_unsupported_family() {
+        let buf = Body::new()
+            .u16(libc::AF_UNIX as u16)
+            .zeros(SOCKADDR_STORAGE_LEN - 2)
+            .zeros(12)
+            .notification(sctp_sn_type::SCTP_PEER_ADDR_CHANGE as u16, 0);
+
+        assert_eq!(notification_parse(&buf).unwrap_err(), NotificationParseError::UnsupportedFamily(libc::AF_UNIX as u16));
+    }
+
+    #[test]
+    fn remote_error() {
+        let buf = Body::new()
+            .bytes(&0x000cu16.to_be_bytes())
+            .zeros(2)
+            .i32(5)
+            .bytes(&[0, 12, 0, 4])
+            .notification(sctp_sn_type::SCTP_REMOTE_ERROR as u16, 0);
+
+        match parse(&buf) {
+            Notification::RemoteError(n) => {
+                assert_eq!(n.error(), 12);
+                assert_eq!(n.assoc_id(), AssocId::from(5));
+                assert_eq!(n.data(), &[0, 12, 0, 4]);
+            }
+            n => panic!("unexpected {:?}", n)
+        }
+    }
+
+    #[test]
+    fn send_failed() {
+        let buf = Body::new()
+            .u32(0x1234_5678)
+            .u16(3)
+            .u16(0)
+            .u16(0)
+            .zeros(2)
+            .u32(42)
+            .u32(9)
+            .u32(1500)
+            .u32(0)
+            .u32(0)
+            .i32(6)
+            .i32(6)
+            .bytes(b"lost")
+            .notification(sctp_sn_type::SCTP_SEND_FAILED as u16, 1);
+
+        match parse(&buf) {
+            Notification::SendFailed(n) => {
+                assert_eq!(n.state(), SendFailedState::DataSent);
+                assert_eq!(n.error(), 0x1234_5678);
+                assert_eq!(n.info().stream(), 3);
+                assert_eq!(n.info().ppid(), 42);
+                assert_eq!(n.info().context(), 9);
+                assert_eq!(n.ttl(), Duration::from_millis(1500));
+                assert_eq!(n.assoc_id(), AssocId::from(6));
+                assert_eq!(n.data(), b"lost");
+            }
+            n => panic!("unexpected {:?}", n)
+        }
+    }
+
+    #[test]
+    fn shutdown() {
+        let buf = Body::new().i32(7).notification(sctp_sn_type::SCTP_SHUTDOWN_EVENT as u16, 0);
+
+        match parse(&buf) {
+            Notification::Shutdown(n) => assert_eq!(n.assoc_id(), AssocId::from(7)),
+            n => panic!("unexpected {:?}", n)
+        }
+    }
+
+    #[test]
+    fn adaptation() {
+        let buf = Body::new().u32(0xabcd).i32(8).notification(sctp_sn_type::SCTP_ADAPTATION_INDICATION as u16, 0);
+
+        match parse(&buf) {
+            Notification::Adaptation(n) => {
+                assert_eq!(n.adaptation_ind(), 0xabcd);
+                assert_eq!(n.assoc_id(), AssocId::from(8));
+            }
+            n => panic!("unexpected {:?}", n)
+        }
+    }
+
+    #[test]
+    fn partial_delivery() {
+        // Newer kernels append the stream and sequence number
+        let buf = Body::new()
+            .u32(SCTP_PARTIAL_DELIVERY_ABORTED)
+            .i32(9)
+            .u32(1)
+            .u32(2)
+            .notification(sctp_sn_type::SCTP_PARTIAL_DELIVERY_EVENT as u16, 0);
+
+        match parse(&buf) {
+            Notification::PartialDelivery(n) => {
+                assert_eq!(n.indication(), PartialDeliveryIndication::Aborted);
+                assert_eq!(n.assoc_id(), AssocId::from(9));
+                assert_eq!(n.stream(), Some(1));
+                assert_eq!(n.seq(), Some(2));
+            }
+            n => panic!("unexpected {:?}", n)
+        }
+    }
+
+    #[test]
+    fn partial_delivery_without_stream() {
+        let buf = Body::new()
+            .u32(SCTP_PARTIAL_DELIVERY_ABORTED)
+            .i32(9)
+            .notification(sctp_sn_type::SCTP_PARTIAL_DELIVERY_EVENT as u16, 0);
+
+        match parse(&buf) {
+            Notification::PartialDelivery(n) => {
+                assert_eq!(n.assoc_id(), AssocId::from(9));
+                assert_eq!(n.stream(), None);
+                assert_eq!(n.seq(), None);
+            }
+            n => panic!("unexpected {:?}", n)
+        }
+    }
+
+    #[test]
+    fn authkey() {
+        let buf = Body::new()
+            .u16(1)
+            .u16(2)
+            .u32(SCTP_AUTH_NO_AUTH)
+            .i32(10)
+            .notification(sctp_sn_type::SCTP_AUTHENTICATION_INDICATION as u16, 0);
+
+        match parse(&buf) {
+            Notification::Authkey(n) => {
+                assert_eq!(n.keynumber(), 1);
+                assert_eq!(n.altkeynumber(), 2);
+                assert_eq!(n.indication(), AuthIndication::NoAuth);
+                assert_eq!(n.assoc_id(), AssocId::from(10));
+            }
+            n => panic!("unexpected {:?}", n)
+        }
+    }
+
+    #[test]
+    fn sender_dry() {
+        let buf = Body::new().i32(11).notification(sctp_sn_type::SCTP_SENDER_DRY_EVENT as u16, 0);
+
+        match parse(&buf) {
+            Notification::SenderDry(n) => assert_eq!(n.assoc_id(), AssocId::from(11)),
+            n => panic!("unexpected {:?}", n)
+        }
+    }
+
+    #[test]
+    fn stream_reset() {
+        let buf = Body::new()
+            .i32(3)
+            .u16(1)
+            .u16(4)
+            .notification(sctp_sn_type::SCTP_STREAM_RESET_EVENT as u16, SCTP_STREAM_RESET_OUTGOING_SSN | SCTP_STREAM_RESET_DENIED);
+
+        match parse(&buf) {
+            Notification::StreamReset(n) => {
+                assert!(n.outgoing());
+                assert!(!n.incoming());
+                assert!(n.denied());
+                assert!(!n.failed());
+                assert_eq!(n.assoc_id(), AssocId::from(3));
+                assert_eq!(n.streams(), &[1, 4]);
+            }
+            n => panic!("unexpected {:?}", n)
+        }
+    }
+
+    #[test]
+    fn assoc_reset() {
+        let buf = Body::new()
+            .i32(3)
+            .u32(1000)
+            .u32(2000)
+            .notification(sctp_sn_type::SCTP_ASSOC_RESET_EVENT as u16, SCTP_ASSOC_RESET_FAILED);
+
+        match parse(&buf) {
+            Notification::AssocReset(n) => {
+                assert!(n.failed());
+                assert!(!n.denied());
+                assert_eq!(n.assoc_id(), AssocId::from(3));
+                assert_eq!(n.local_tsn(), 1000);
+                assert_eq!(n.remote_tsn(), 2000);
+            }
+            n => panic!("unexpected {:?}", n)
+        }
+    }
+
+    #[test]
+    fn stream_change() {
+        let buf = Body::new()
+            .i32(3)
+            .u16(10)
+            .u16(20)
+            .notification(sctp_sn_type::SCTP_STREAM_CHANGE_EVENT as u16, 0);
+
+        match parse(&buf) {
+            Notification::StreamChange(n) => {
+                assert!(!n.denied());
+                assert!(!n.failed());
+                assert_eq!(n.assoc_id(), AssocId::from(3));
+                assert_eq!(n.inbound_streams(), 10);
+                assert_eq!(n.outbound_streams(), 20);
+            }
+            n => panic!("unexpected {:?}", n)
+        }
+    }
+
+    #[test]
+    fn send_failed_event() {
+        let buf = Body::new()
+            .u32(0x0001_0002)
+            .u16(5)
+            .u16(0)
+            .u32(42)
+            .u32(9)
+            .i32(0)
+            .i32(7)
+            .bytes(b"lost")
+            .notification(sctp_sn_type::SCTP_SEND_FAILED_EVENT as u16, 0);
+
+        match parse(&buf) {
+            Notification::SendFailedEvent(n) => {
+                assert_eq!(n.state(), SendFailedState::DataUnsent);
+                assert_eq!(n.error(), 0x0001_0002);
+                assert_eq!(n.stream(), 5);
+                assert_eq!(n.ppid(), 42);
+                assert_eq!(n.context(), 9);
+                assert_eq!(n.assoc_id(), AssocId::from(7));
+                assert_eq!(n.data(), b"lost");
+            }
+            n => panic!("unexpected {:?}", n)
+        }
+    }
+
+    #[test]
+    fn data_stops_at_length() {
+        let mut buf = Body::new()
+            .u16(0)
+            .u16(0)
+            .u16(1)
+            .u16(1)
+            .i32(1)
+            .bytes(b"abc")
+            .notification(sctp_sn_type::SCTP_ASSOC_CHANGE as u16, 0);
+        buf.extend_from_slice(b"next message");
+
+        match parse(&buf) {
+            Notification::AssocChange(n) => assert_eq!(n.data(), b"abc"),
+            n => panic!("unexpected {:?}", n)
+        }
+    }
+
+    #[test]
+    fn truncated_header() {
+        assert_eq!(notification_parse(&[0x01, 0x80, 0, 0]).unwrap_err(),
+                   NotificationParseError::Truncated { expected: 8, actual: 4 });
+    }
+
+    #[test]
+    fn truncated_body() {
+        let buf = Body::new().i32(1).notification(sctp_sn_type::SCTP_SHUTDOWN_EVENT as u16, 0);
+
+        assert_eq!(notification_parse(&buf[..10]).unwrap_err(),
+                   NotificationParseError::Truncated { expected: 12, actual: 10 });
+    }
+
+    #[test]
+    fn invalid_length() {
+        let buf = Body::new().notification(sctp_sn_type::SCTP_SHUTDOWN_EVENT as u16, 0);
+
+        assert_eq!(notification_parse(&buf).unwrap_err(),
+                   NotificationParseError::InvalidLength { sn_type: sctp_sn_type::SCTP_SHUTDOWN_EVENT as u16, length: 8 });
+    }
+
+    #[test]
+    fn unknown_type() {
+        let buf = Body::new().i32(1).notification(0x1234, 0);
+
+        assert!(notification_parse(&buf).unwrap().is_none());
+    }
 }
@@ -0,0 +1,431 @@
+use std::cmp;
+use std::mem;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use sys;
+use sys::sctp_spp_flags::*;
+
+use net::addr::addr_to_storage;
+
+/// Whole milliseconds of `dur`, saturating at `max`
+#[doc(hidden)]
+pub fn millis(dur: Duration, max: u64) -> u64 {
+    cmp::min(dur.as_millis(), max as u128) as u64
+}
+
+/// Parameters used when setting up new associations (`SCTP_INITMSG`), a zero field keeps the
+/// system default
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct InitParams {
+    num_ostreams: u16,
+    max_instreams: u16,
+    max_attempts: u16,
+    max_init_timeo: Duration
+}
+
+impl InitParams {
+    #[inline]
+    pub fn new() -> InitParams {
+        InitParams::default()
+    }
+
+    /// Number of outbound streams to request
+    #[inline]
+    pub fn num_ostreams(mut self, num_ostreams: u16) -> InitParams {
+        self.num_ostreams = num_ostreams;
+        self
+    }
+
+    /// Maximum number of inbound streams to accept
+    #[inline]
+    pub fn max_instreams(mut self, max_instreams: u16) -> InitParams {
+        self.max_instreams = max_instreams;
+        self
+    }
+
+    /// How many times INIT is retransmitted before giving up
+    #[inline]
+    pub fn max_attempts(mut self, max_attempts: u16) -> InitParams {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Upper bound of the INIT retransmission timeout, in whole milliseconds. Setting more
+    /// than 65535 ms fails with `InvalidInput`
+    #[inline]
+    pub fn max_init_timeo(mut self, max_init_timeo: Duration) -> InitParams {
+        self.max_init_timeo = max_init_timeo;
+        self
+    }
+
+    #[inline]
+    pub fn get_num_ostreams(&self) -> u16 {
+        self.num_ostreams
+    }
+
+    #[inline]
+    pub fn get_max_instreams(&self) -> u16 {
+        self.max_instreams
+    }
+
+    #[inline]
+    pub fn get_max_attempts(&self) -> u16 {
+        self.max_attempts
+    }
+
+    #[inline]
+    pub fn get_max_init_timeo(&self) -> Duration {
+        self.max_init_timeo
+    }
+}
+
+impl<'a> From<&'a InitParams> for sys::sctp_initmsg {
+    fn from(params: &'a InitParams) -> sys::sctp_initmsg {
+        sys::sctp_initmsg {
+            sinit_num_ostreams: params.num_ostreams,
+            sinit_max_instreams: params.max_instreams,
+            sinit_max_attempts: params.max_attempts,
+            sinit_max_init_timeo: millis(params.max_init_timeo, u16::MAX as u64) as u16
+        }
+    }
+}
+
+impl From<sys::sctp_initmsg> for InitParams {
+    fn from(initmsg: sys::sctp_initmsg) -> InitParams {
+        InitParams {
+            num_ostreams: initmsg.sinit_num_ostreams,
+            max_instreams: initmsg.sinit_max_instreams,
+            max_attempts: initmsg.sinit_max_attempts,
+            max_init_timeo: Duration::from_millis(initmsg.sinit_max_init_timeo as u64)
+        }
+    }
+}
+
+/// Retransmission timeout parameters (`SCTP_RTOINFO`), a zero field is left unchanged when set
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RtoInfo {
+    initial: Duration,
+    min: Duration,
+    max: Duration
+}
+
+impl RtoInfo {
+    #[inline]
+    pub fn new() -> RtoInfo {
+        RtoInfo::default()
+    }
+
+    /// The RTO used before any RTT measurement
+    #[inline]
+    pub fn initial(mut self, initial: Duration) -> RtoInfo {
+        self.initial = initial;
+        self
+    }
+
+    #[inline]
+    pub fn min(mut self, min: Duration) -> RtoInfo {
+        self.min = min;
+        self
+    }
+
+    #[inline]
+    pub fn max(mut self, max: Duration) -> RtoInfo {
+        self.max = max;
+        self
+    }
+
+    #[inline]
+    pub fn get_initial(&self) -> Duration {
+        self.initial
+    }
+
+    #[inline]
+    pub fn get_min(&self) -> Duration {
+        self.min
+    }
+
+    #[inline]
+    pub fn get_max(&self) -> Duration {
+        self.max
+    }
+
+    pub fn to_raw(&self, assoc_id: sys::sctp_assoc_t) -> sys::sctp_rtoinfo {
+        sys::sctp_rtoinfo {
+            srto_assoc_id: assoc_id,
+            srto_initial: millis(self.initial, u32::MAX as u64) as u32,
+            srto_max: millis(self.max, u32::MAX as u64) as u32,
+            srto_min: millis(self.min, u32::MAX as u64) as u32
+        }
+    }
+}
+
+impl From<sys::sctp_rtoinfo> for RtoInfo {
+    fn from(rtoinfo: sys::sctp_rtoinfo) -> RtoInfo {
+        RtoInfo {
+            initial: Duration::from_millis(rtoinfo.srto_initial as u64),
+            min: Duration::from_millis(rtoinfo.srto_min as u64),
+            max: Duration::from_millis(rtoinfo.srto_max as u64)
+        }
+    }
+}
+
+/// Association parameters (`SCTP_ASSOCINFO`), `peer_destinations`, `peer_rwnd` and `local_rwnd`
+/// are only reported by the kernel, a zero `max_retrans` or `cookie_life` is left unchanged
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AssocParams {
+    max_retrans: u16,
+    peer_destinations: u16,
+    peer_rwnd: u32,
+    local_rwnd: u32,
+    cookie_life: Duration
+}
+
+impl AssocParams {
+    #[inline]
+    pub fn new() -> AssocParams {
+        AssocParams::default()
+    }
+
+    /// Retransmissions across all paths before the association is considered unreachable
+    #[inline]
+    pub fn max_retrans(mut self, max_retrans: u16) -> AssocParams {
+        self.max_retrans = max_retrans;
+        self
+    }
+
+    /// Lifetime of the state cookie handed out to peers
+    #[inline]
+    pub fn cookie_life(mut self, cookie_life: Duration) -> AssocParams {
+        self.cookie_life = cookie_life;
+        self
+    }
+
+    #[inline]
+    pub fn get_max_retrans(&self) -> u16 {
+        self.max_retrans
+    }
+
+    #[inline]
+    pub fn get_cookie_life(&self) -> Duration {
+        self.cookie_life
+    }
+
+    #[inline]
+    pub fn peer_destinations(&self) -> u16 {
+        self.peer_destinations
+    }
+
+    #[inline]
+    pub fn peer_rwnd(&self) -> u32 {
+        self.peer_rwnd
+    }
+
+    #[inline]
+    pub fn local_rwnd(&self) -> u32 {
+        self.local_rwnd
+    }
+
+    pub fn to_raw(&self, assoc_id: sys::sctp_assoc_t) -> sys::sctp_assocparams {
+        sys::sctp_assocparams {
+            sasoc_assoc_id: assoc_id,
+            sasoc_asocmaxrxt: self.max_retrans,
+            sasoc_number_peer_destinations: 0,
+            sasoc_peer_rwnd: 0,
+            sasoc_local_rwnd: 0,
+            sasoc_cookie_life: millis(self.cookie_life, u32::MAX as u64) as u32
+        }
+    }
+}
+
+impl From<sys::sctp_assocparams> for AssocParams {
+    fn from(params: sys::sctp_assocparams) -> AssocParams {
+        AssocParams {
+            max_retrans: params.sasoc_asocmaxrxt,
+            peer_destinations: params.sasoc_number_peer_destinations,
+            peer_rwnd: params.sasoc_peer_rwnd,
+            local_rwnd: params.sasoc_local_rwnd,
+            cookie_life: Duration::from_millis(params.sasoc_cookie_life as u64)
+        }
+    }
+}
+
+/// Heartbeat, retransmission, path MTU and SACK delay settings of a peer address or of a whole
+/// association (`SCTP_PEER_ADDR_PARAMS`), only the settings that were set are changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PeerAddrParams {
+    hb_interval: u32,
+    path_max_retrans: u16,
+    path_mtu: u32,
+    sack_delay: u32,
+    flags: u32
+}
+
+impl PeerAddrParams {
+    #[inline]
+    pub fn new() -> PeerAddrParams {
+        PeerAddrParams::default()
+    }
+
+    /// Enable heartbeats with the given interval, or disable them with `None`
+    pub fn heartbeat(mut self, interval: Option<Duration>) -> PeerAddrParams {
+        self.flags &= !(SPP_HB as u32);
+
+        match interval {
+            Some(interval) => {
+                self.hb_interval = millis(interval, u32::MAX as u64) as u32;
+                self.flags |= SPP_HB_ENABLE as u32;
+
+                if self.hb_interval == 0 {
+                    self.flags |= SPP_HB_TIME_IS_ZERO as u32;
+                } else {
+                    self.flags &= !(SPP_HB_TIME_IS_ZERO as u32);
+                }
+            }
+            None => {
+                self.hb_interval = 0;
+                self.flags |= SPP_HB_DISABLE as u32;
+            }
+        }
+
+        self
+    }
+
+    /// Send a heartbeat immediately
+    #[inline]
+    pub fn heartbeat_now(mut self) -> PeerAddrParams {
+        self.flags |= SPP_HB_DEMAND as u32;
+        self
+    }
+
+    #[inline]
+    pub fn path_max_retrans(mut self, path_max_retrans: u16) -> PeerAddrParams {
+        self.path_max_retrans = path_max_retrans;
+        self
+    }
+
+    /// Use a fixed path MTU and disable path MTU discovery, or enable discovery with `None`
+    pub fn path_mtu(mut self, mtu: Option<u32>) -> PeerAddrParams {
+        self.flags &= !(SPP_PMTUD as u32);
+
+        match mtu {
+            Some(mtu) => {
+                self.path_mtu = mtu;
+                self.flags |= SPP_PMTUD_DISABLE as u32;
+            }
+            None => {
+                self.path_mtu = 0;
+                self.flags |= SPP_PMTUD_ENABLE as u32;
+            }
+        }
+
+        self
+    }
+
+    /// Delay SACKs by up to the given time, or send them immediately with `None`
+    pub fn sack_delay(mut self, delay: Option<Duration>) -> PeerAddrParams {
+        self.flags &= !(SPP_SACKDELAY as u32);
+
+        match delay {
+            Some(delay) => {
+                self.sack_delay = millis(delay, u32::MAX as u64) as u32;
+                self.flags |= SPP_SACKDELAY_ENABLE as u32;
+            }
+            None => {
+                self.sack_delay = 0;
+                self.flags |= SPP_SACKDELAY_DISABLE as u32;
+            }
+        }
+
+        self
+    }
+
+    /// The heartbeat interval, `None` if heartbeats are disabled
+    pub fn get_heartbeat(&self) -> Option<Duration> {
+        if self.flags & SPP_HB_DISABLE as u32 != 0 {
+            None
+        } else {
+            Some(Duration::from_millis(self.hb_interval as u64))
+        }
+    }
+
+    #[inline]
+    pub fn get_path_max_retrans(&self) -> u16 {
+        self.path_max_retrans
+    }
+
+    #[inline]
+    pub fn get_path_mtu(&self) -> u32 {
+        self.path_mtu
+    }
+
+    #[inline]
+    pub fn pmtud_enabled(&self) -> bool {
+        self.flags & SPP_PMTUD_DISABLE as u32 == 0
+    }
+
+    /// The SACK delay, `None` if delayed SACKs are disabled
+    pub fn get_sack_delay(&self) -> Option<Duration> {
+        if self.flags & SPP_SACKDELAY_DISABLE as u32 != 0 {
+            None
+        } else {
+            Some(Duration::from_millis(self.sack_delay as u64))
+        }
+    }
+
+    pub fn to_raw(&self, assoc_id: sys::sctp_assoc_t, addr: Option<&SocketAddr>) -> sys::sctp_paddrparams {
+        let mut raw: sys::sctp_paddrparams = unsafe { mem::zeroed() };
+
+        raw.spp_assoc_id = assoc_id;
+        if let Some(addr) = addr {
+            raw.spp_address = addr_to_storage(addr);
+        }
+        raw.spp_hbinterval = self.hb_interval;
+        raw.spp_pathmaxrxt = self.path_max_retrans;
+        raw.spp_pathmtu = self.path_mtu;
+        raw.spp_sackdelay = self.sack_delay;
+        raw.spp_flags = self.flags;
+
+        raw
+    }
+}
+
+impl From<sys::sctp_paddrparams> for PeerAddrParams {
+    fn from(raw: sys::sctp_paddrparams) -> PeerAddrParams {
+        PeerAddrParams {
+            hb_interval: raw.spp_hbinterval,
+            path_max_retrans: raw.spp_pathmaxrxt,
+            path_mtu: raw.spp_pathmtu,
+            sack_delay: raw.spp_sackdelay,
+            flags: raw.spp_flags
+        }
+    }
+}
+
+/// HMAC algorithms for SCTP-AUTH (RFC 4895)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HmacAlgo {
+    Sha1,
+    Sha256,
+    Other(u16)
+}
+
+impl HmacAlgo {
+    pub fn ident(&self) -> u16 {
+        match *self {
+            HmacAlgo::Sha1 => sys::SCTP_AUTH_HMAC_ID_SHA1 as u16,
+            HmacAlgo::Sha256 => sys::SCTP_AUTH_HMAC_ID_SHA256 as u16,
+            HmacAlgo::Other(ident) => ident
+        }
+    }
+}
+
+impl From<u16> for HmacAlgo {
+    fn from(ident: u16) -> HmacAlgo {
+        match ident as u32 {
+            sys::SCTP_AUTH_HMAC_ID_SHA1 => HmacAlgo::Sha1,
+            sys::SCTP_AUTH_HMAC_ID_SHA256 => HmacAlgo::Sha256,
+            _ => HmacAlgo::Other(ident)
+        }
+    }
+}
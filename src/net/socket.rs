@@ -1,9 +1,10 @@
-use std::io::{self, Error, ErrorKind};
-use std::net::SocketAddr;
+use std::io::{self, Error, ErrorKind, IoSlice, IoSliceMut};
+use std::net::{SocketAddr, Shutdown};
 use std::ptr;
 use std::time::{Duration};
 use std::mem;
 use std::cmp;
+use std::slice;
 use std::os::unix::io::{AsRawFd, RawFd, FromRawFd};
 
 use libc;
@@ -12,14 +13,17 @@ use net::cvt;
 use net::cvt_r;
 use net::{AsInner, FromInner, IntoInner};
 use net::fd::FileDesc;
-use net::addr::sockaddr_to_addr;
+use net::addr::{sockaddr_to_addr, addr_to_storage};
 use net::fd;
 use net::event::Event;
 use net::notification::{notification_parse, Notification};
+use net::info::{RecvInfo, NxtInfo, SendOptions};
+use net::message::MessageBuf;
+use net::opt::{InitParams, RtoInfo, AssocParams, PeerAddrParams, HmacAlgo};
+use net::status::{AssocStatus, PathInfo, AssocStats};
 
 use sys;
 
-#[allow(dead_code)]
 pub enum BindOp {
     /// Add bind addresses
     AddAddr,
@@ -29,10 +33,10 @@ pub enum BindOp {
 
 impl BindOp {
     fn flag(&self) -> libc::c_int {
-        return match *self {
+        match *self {
             BindOp::AddAddr => sys::SCTP_BINDX_ADD_ADDR,
             BindOp::RemAddr => sys::SCTP_BINDX_REM_ADDR
-        };
+        }
     }
 }
 
@@ -43,18 +47,109 @@ enum SctpAddrType {
 
 impl SctpAddrType {
     unsafe fn get(&self, sock: libc::c_int, id: sys::sctp_assoc_t, ptr: *mut *mut libc::sockaddr) -> libc::c_int {
-        return match *self {
+        match *self {
             SctpAddrType::Local => sys::sctp_getladdrs(sock, id, ptr),
             SctpAddrType::Peer => sys::sctp_getpaddrs(sock, id, ptr)
-        };
+        }
     }
 
     unsafe fn free(&self, ptr: *mut libc::sockaddr) -> libc::c_int {
-        return match *self {
+        match *self {
             SctpAddrType::Local => sys::sctp_freeladdrs(ptr),
             SctpAddrType::Peer => sys::sctp_freepaddrs(ptr)
+        }
+    }
+}
+
+/// Pack `addrs` back to back as `sockaddr_in`/`sockaddr_in6`, the layout `sctp_bindx` and
+/// `sctp_connectx` expect
+fn pack_addrs(addrs: &[SocketAddr]) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(addrs.len() * mem::size_of::<libc::sockaddr_in6>());
+
+    for addr in addrs {
+        let (addrp, len) = addr.into_inner();
+        let bytes = unsafe { slice::from_raw_parts(addrp.as_ptr() as *const u8, len as usize) };
+        buf.extend_from_slice(bytes);
+    }
+
+    buf
+}
+
+/// Unpack `count` addresses packed back to back as `sockaddr_in`/`sockaddr_in6`
+unsafe fn unpack_addrs(packed: *const u8, count: usize) -> io::Result<Vec<SocketAddr>> {
+    let mut addrs = Vec::with_capacity(count);
+    let mut offset = 0;
+
+    for _ in 0..count {
+        let sockaddr = packed.add(offset) as *const libc::sockaddr;
+
+        let len = match (*sockaddr).sa_family as i32 {
+            libc::AF_INET => mem::size_of::<libc::sockaddr_in>(),
+            libc::AF_INET6 => mem::size_of::<libc::sockaddr_in6>(),
+            family => return Err(Error::other(format!("Unsupported address family : {}", family)))
         };
+
+        let mut storage: libc::sockaddr_storage = mem::zeroed();
+        ptr::copy_nonoverlapping(sockaddr as *const u8, &mut storage as *mut libc::sockaddr_storage as *mut u8, len);
+
+        addrs.push(sockaddr_to_addr(&storage, len)?);
+        offset += len;
     }
+
+    Ok(addrs)
+}
+
+/// Room for the SCTP ancillary data of one `sendv`/`recvv`, in u64 to keep `cmsghdr` aligned
+const CONTROL_LEN: usize = 32;
+
+unsafe fn push_cmsg<T>(control: &mut [u64], used: &mut usize, kind: sys::sctp_cmsg_type, data: T) {
+    let len = mem::size_of::<T>() as libc::c_uint;
+    let space = libc::CMSG_SPACE(len) as usize;
+    assert!(*used + space <= mem::size_of_val(control));
+
+    let cmsg = (control.as_mut_ptr() as *mut u8).add(*used) as *mut libc::cmsghdr;
+    (*cmsg).cmsg_level = sys::IPPROTO_SCTP;
+    (*cmsg).cmsg_type = kind as libc::c_int;
+    (*cmsg).cmsg_len = libc::CMSG_LEN(len) as _;
+    ptr::write_unaligned(libc::CMSG_DATA(cmsg) as *mut T, data);
+
+    *used += space;
+}
+
+/// Each event with the notification type `SCTP_EVENT` knows it by
+fn event_types() -> Vec<(Event, sys::sctp_sn_type)> {
+    vec![
+        (Event::data_io(), sys::sctp_sn_type::SCTP_SN_TYPE_BASE),
+        (Event::association(), sys::sctp_sn_type::SCTP_ASSOC_CHANGE),
+        (Event::address(), sys::sctp_sn_type::SCTP_PEER_ADDR_CHANGE),
+        (Event::send_failure(), sys::sctp_sn_type::SCTP_SEND_FAILED),
+        (Event::peer_error(), sys::sctp_sn_type::SCTP_REMOTE_ERROR),
+        (Event::shutdown(), sys::sctp_sn_type::SCTP_SHUTDOWN_EVENT),
+        (Event::partial_delivery(), sys::sctp_sn_type::SCTP_PARTIAL_DELIVERY_EVENT),
+        (Event::adaptation_layer(), sys::sctp_sn_type::SCTP_ADAPTATION_INDICATION),
+        (Event::authentication(), sys::sctp_sn_type::SCTP_AUTHENTICATION_INDICATION),
+        (Event::sender_dry(), sys::sctp_sn_type::SCTP_SENDER_DRY_EVENT),
+        (Event::stream_reset(), sys::sctp_sn_type::SCTP_STREAM_RESET_EVENT),
+        (Event::assoc_reset(), sys::sctp_sn_type::SCTP_ASSOC_RESET_EVENT),
+        (Event::stream_change(), sys::sctp_sn_type::SCTP_STREAM_CHANGE_EVENT),
+        (Event::send_failure_event(), sys::sctp_sn_type::SCTP_SEND_FAILED_EVENT)
+    ]
+}
+
+/// The events `SCTP_EVENTS` switches on every kernel, with their byte in `sctp_event_subscribe`
+fn legacy_events(subscribe: &mut sys::sctp_event_subscribe) -> Vec<(Event, &mut u8)> {
+    vec![
+        (Event::data_io(), &mut subscribe.sctp_data_io_event),
+        (Event::association(), &mut subscribe.sctp_association_event),
+        (Event::address(), &mut subscribe.sctp_address_event),
+        (Event::send_failure(), &mut subscribe.sctp_send_failure_event),
+        (Event::peer_error(), &mut subscribe.sctp_peer_error_event),
+        (Event::shutdown(), &mut subscribe.sctp_shutdown_event),
+        (Event::partial_delivery(), &mut subscribe.sctp_partial_delivery_event),
+        (Event::adaptation_layer(), &mut subscribe.sctp_adaptation_layer_event),
+        (Event::authentication(), &mut subscribe.sctp_authentication_event),
+        (Event::sender_dry(), &mut subscribe.sctp_sender_dry_event)
+    ]
 }
 
 pub struct Socket(FileDesc);
@@ -73,7 +168,7 @@ impl Socket {
             match cvt(libc::socket(fam, ty | libc::SOCK_CLOEXEC, sys::IPPROTO_SCTP)) {
                 Ok(fd) => {
                     let fd = FileDesc::new(fd);
-                    let socket = Socket(fd);
+                    let socket = Socket::from_inner(fd);
 
                     socket.default_event_subscribe()?;
 
@@ -86,7 +181,7 @@ impl Socket {
             let fd = cvt(libc::socket(fam, ty, sys::IPPROTO_SCTP))?;
             let fd = FileDesc::new(fd);
             fd.set_cloexec()?;
-            let socket = Socket(fd);
+            let socket = Socket::from_inner(fd);
 
             socket.default_event_subscribe()?;
 
@@ -94,13 +189,14 @@ impl Socket {
         }
     }
 
-    pub fn accept(&self, storage: *mut libc::sockaddr, len: *mut libc::socklen_t) -> io::Result<Socket> {;
+    pub fn accept(&self, storage: &mut libc::sockaddr_storage, len: &mut libc::socklen_t) -> io::Result<Socket> {
+        let storage = storage as *mut libc::sockaddr_storage as *mut libc::sockaddr;
         let fd = cvt_r(|| unsafe {
             libc::accept4(self.0.raw(), storage, len, libc::SOCK_CLOEXEC)
         })?;
         let fd = FileDesc::new(fd);
 
-        Ok(Socket(fd))
+        Ok(Socket::from_inner(fd))
     }
 
     pub fn listen(&self, backlog: libc::c_int) -> io::Result<()> {
@@ -111,30 +207,15 @@ impl Socket {
 
     pub fn bind(&self, addr: &SocketAddr) -> io::Result<()> {
         let (addrp, len) = addr.into_inner();
-        cvt(unsafe { libc::bind(self.0.raw(), addrp, len as _)})?;
+        cvt(unsafe { libc::bind(self.0.raw(), addrp.as_ptr(), len as _)})?;
 
         Ok(())
     }
 
     pub fn bindx(&self, addrs: &[SocketAddr], op: BindOp) -> io::Result<()> {
-        let buf: *mut u8 = unsafe {
-            libc::malloc((addrs.len() * mem::size_of::<libc::sockaddr_in6>()) as libc::size_t) as *mut u8
-        };
+        let buf = pack_addrs(addrs);
 
-        let mut offset = 0isize;
-        for addr in addrs {
-            let (addrp, len) = addr.into_inner();
-
-            unsafe {
-                ptr::copy_nonoverlapping(addrp as *mut u8, buf.offset(offset), len as usize);
-            }
-
-            offset += len as isize;
-        }
-
-        cvt( unsafe { sys::sctp_bindx(self.0.raw(), buf as *const libc::sockaddr, addrs.len() as i32, op.flag()) })?;
-
-        unsafe { libc::free(buf as *mut libc::c_void); }
+        cvt(unsafe { sys::sctp_bindx(self.0.raw(), buf.as_ptr() as *const libc::sockaddr, addrs.len() as i32, op.flag()) })?;
 
         Ok(())
     }
@@ -142,32 +223,17 @@ impl Socket {
     pub fn connect(&self, addr: &SocketAddr) -> io::Result<()> {
         let (addrp, len) = addr.into_inner();
             
-        cvt_r(|| unsafe { libc::connect(self.0.raw(), addrp, len) })?;
+        cvt_r(|| unsafe { libc::connect(self.0.raw(), addrp.as_ptr(), len) })?;
 
         Ok(())
     }
 
     pub fn connectx(&self, addrs: &[SocketAddr]) -> io::Result<sys::sctp_assoc_t> {
-        let buf: *mut u8 = unsafe {
-            libc::malloc((addrs.len() * mem::size_of::<libc::sockaddr_in6>()) as libc::size_t) as *mut u8
-        };
-
-        let mut offset = 0isize;
-        for addr in addrs {
-            let (addrp, len) = addr.into_inner();
-
-            unsafe {
-                ptr::copy_nonoverlapping(addrp as *mut u8, buf.offset(offset), len as usize);
-            }
-
-            offset += len as isize;
-        }
+        let buf = pack_addrs(addrs);
 
         let mut assoc: sys::sctp_assoc_t = 0;
 
-        cvt_r(|| unsafe { sys::sctp_connectx(self.0.raw(), buf as *const libc::sockaddr, addrs.len() as i32, &mut assoc) })?;
-
-        unsafe { libc::free(buf as *mut libc::c_void); }
+        cvt_r(|| unsafe { sys::sctp_connectx(self.0.raw(), buf.as_ptr() as *const libc::sockaddr, addrs.len() as i32, &mut assoc) })?;
 
         Ok(assoc)
     }
@@ -175,38 +241,21 @@ impl Socket {
     fn addrs(&self, id: sys::sctp_assoc_t, what: SctpAddrType) -> io::Result<Vec<SocketAddr>> {
         let mut addrs: *mut u8 = ptr::null_mut();
 
-        let len = unsafe { what.get(self.0.raw(), id, mem::transmute(&mut addrs)) };
-
-        if len < 0 {
-            return Err(Error::new(ErrorKind::Other, "Cannot retrieve addresses"))
-        }
-
-        if len == 0 {
-            return Err(Error::new(ErrorKind::AddrNotAvailable, "Socket is unbound"))
-        }
-
-        let mut addrs2 = Vec::with_capacity(len as usize);
-        let mut offset = 0;
+        let len = unsafe { what.get(self.0.raw(), id, &mut addrs as *mut *mut u8 as *mut *mut libc::sockaddr) };
 
-        for _ in 0..len {
-            let scokaddr = unsafe { addrs.offset(offset) } as *const libc::sockaddr;
-
-            let len = match unsafe { (*scokaddr).sa_family } as i32 {
-                libc::AF_INET => mem::size_of::<libc::sockaddr_in>(),
-                libc::AF_INET6 => mem::size_of::<libc::sockaddr_in6>(),
-                family => {
-                    unsafe { what.free(addrs as *mut libc::sockaddr) };
-                    return Err(Error::new(ErrorKind::Other, format!("Unsupported address family : {}", family)))
-                }
-            } as libc::socklen_t;
+        let result = if len < 0 {
+            Err(Error::other("Cannot retrieve addresses"))
+        } else if len == 0 {
+            Err(Error::new(ErrorKind::AddrNotAvailable, "Socket is unbound"))
+        } else {
+            unsafe { unpack_addrs(addrs, len as usize) }
+        };
 
-            addrs2.push(sockaddr_to_addr(unsafe { &*(scokaddr as *const libc::sockaddr_storage) }, len as usize)?);
-            offset += len as isize;
+        if !addrs.is_null() {
+            unsafe { what.free(addrs as *mut libc::sockaddr) };
         }
 
-        unsafe { what.free(addrs as *mut libc::sockaddr) };
-
-        Ok(addrs2)
+        result
     }
 
     pub fn local_addrs(&self, id: sys::sctp_assoc_t) -> io::Result<Vec<SocketAddr>> {
@@ -217,8 +266,16 @@ impl Socket {
         self.addrs(id, SctpAddrType::Peer)
     }
 
+    pub fn peeloff(&self, id: sys::sctp_assoc_t) -> io::Result<Socket> {
+        let fd = cvt(unsafe { sys::sctp_peeloff(self.0.raw(), id) })?;
+        let fd = FileDesc::new(fd);
+        fd.set_cloexec()?;
+
+        Ok(Socket::from_inner(fd))
+    }
+
     pub fn duplicate(&self) -> io::Result<Socket> {
-        self.0.duplicate().map(Socket)
+        self.0.duplicate().map(Socket::from_inner)
     }
 
     pub fn read(&self, buf: &mut [u8]) -> io::Result<usize> {
@@ -242,14 +299,14 @@ impl Socket {
         self.0.write(buf)
     }
 
-    pub fn recvmsg(&self, msg: &mut [u8]) -> io::Result<(usize, u16, Option<SocketAddr>, Option<Notification>)> {
+    fn recvmsg_raw(&self, msg: &mut [u8]) -> io::Result<(usize, sys::sctp_sndrcvinfo, libc::c_int, Option<SocketAddr>)> {
         let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
         let mut len = mem::size_of_val(&storage) as libc::socklen_t;
 
         let mut info: sys::sctp_sndrcvinfo = unsafe { mem::zeroed() };
         let mut flags: libc::c_int = 0;
 
-        let ret = cvt(unsafe {
+        let ret = cvt_r(|| unsafe {
             sys::sctp_recvmsg(
                 self.0.raw(),
                 msg.as_mut_ptr() as *mut libc::c_void,
@@ -261,45 +318,224 @@ impl Socket {
             )
         })?;
 
-        let notification = if flags & sys::MSG_NOTIFICATION == sys::MSG_NOTIFICATION {
-            let buf: &[u8] = &msg[0..(ret as usize)];
+        let addr = sockaddr_to_addr(&storage, len as usize).ok();
+
+        Ok((ret as usize, info, flags, addr))
+    }
+
+    /// Notifications are only parsed once they are complete (`MSG_EOR` set), otherwise the
+    /// returned `RecvInfo` reports the partial read
+    pub fn recvmsg(&self, msg: &mut [u8]) -> io::Result<(usize, RecvInfo, Option<SocketAddr>, Option<Notification>)> {
+        let (size, info, flags, addr) = self.recvmsg_raw(msg)?;
+
+        let notification = if flags & sys::MSG_NOTIFICATION == sys::MSG_NOTIFICATION && flags & libc::MSG_EOR == libc::MSG_EOR {
+            let buf: &[u8] = &msg[0..size];
             notification_parse(buf)?
         } else {
             None
         };
 
-        let addr = match sockaddr_to_addr(&storage, len as usize) {
-            Ok(addr) => Some(addr),
-            Err(_) => None
+        Ok((size, RecvInfo::from((info, flags)), addr, notification))
+    }
+
+    /// Receive a whole message into `msg`, see `MessageBuf` for messages interrupted by
+    /// `WouldBlock`
+    pub fn recv_message(&self, msg: &mut MessageBuf) -> io::Result<(usize, RecvInfo, Option<SocketAddr>, Option<Notification>)> {
+        let (size, flags, (info, addr)) = msg.fill(|buf| {
+            let (size, info, flags, addr) = self.recvmsg_raw(buf)?;
+            Ok((size, flags, (info, addr)))
+        })?;
+
+        let notification = if flags & sys::MSG_NOTIFICATION == sys::MSG_NOTIFICATION && size != 0 {
+            notification_parse(msg.as_slice())?
+        } else {
+            None
         };
 
-        Ok((ret as usize, info.sinfo_stream, addr, notification))
+        Ok((size, RecvInfo::from((info, flags)), addr, notification))
     }
 
-    pub fn sendmsg(&self, msg: &[u8], addr: Option<SocketAddr>, stream: u16, ttl: u32) -> io::Result<usize> {
+    /// SCTP queues a message whole or not at all: on `WouldBlock` nothing was sent and the
+    /// whole message has to be sent again
+    pub fn sendmsg(&self, msg: &[u8], addr: Option<SocketAddr>, options: &SendOptions) -> io::Result<usize> {
+        if options.needs_sendv() {
+            return self.sendv(&[IoSlice::new(msg)], addr, 0, options)
+        }
+
+        let info = sys::sctp_sndrcvinfo::from(options);
+
+        let addr = addr.as_ref().map(|addr| addr.into_inner());
 
         let (addrp, len) = match addr {
-            Some(addr) => addr.into_inner(),
+            Some((ref addrp, len)) => (addrp.as_ptr(), len),
             None => (ptr::null(), 0)
         };
 
-        let ret = cvt(unsafe {
+        let ret = cvt_r(|| unsafe {
             sys::sctp_sendmsg(
                 self.0.raw(),
                 msg.as_ptr() as *const libc::c_void,
                 cmp::min(msg.len(), fd::max_len()),
                 addrp,
                 len,
-                0,
-                0,
-                stream,
-                ttl,
+                info.sinfo_ppid,
+                info.sinfo_flags as u32,
+                info.sinfo_stream,
+                info.sinfo_timetolive,
+                info.sinfo_context
+            )
+        })?;
+
+        Ok(ret as usize)
+
+    }
+
+    pub fn send(&self, msg: &[u8], info: &sys::sctp_sndrcvinfo) -> io::Result<usize> {
+        let ret = cvt_r(|| unsafe {
+            sys::sctp_send(
+                self.0.raw(),
+                msg.as_ptr() as *const libc::c_void,
+                cmp::min(msg.len(), fd::max_len()),
+                info,
                 0
             )
         })?;
 
         Ok(ret as usize)
+    }
+
+    /// `sctp_sendv` equivalent: gather `bufs` into one message, with SCTP_SNDINFO and,
+    /// when set in `options`, SCTP_PRINFO and SCTP_AUTHINFO passed as control messages
+    pub fn sendv(&self, bufs: &[IoSlice], addr: Option<SocketAddr>, id: sys::sctp_assoc_t, options: &SendOptions) -> io::Result<usize> {
+        let mut control = [0u64; CONTROL_LEN];
+        let mut used = 0;
+
+        unsafe {
+            push_cmsg(&mut control, &mut used, sys::sctp_cmsg_type::SCTP_SNDINFO, options.to_sndinfo(id));
+
+            if let Some(prinfo) = options.to_prinfo() {
+                push_cmsg(&mut control, &mut used, sys::sctp_cmsg_type::SCTP_PRINFO, prinfo);
+            }
+
+            if let Some(authinfo) = options.to_authinfo() {
+                push_cmsg(&mut control, &mut used, sys::sctp_cmsg_type::SCTP_AUTHINFO, authinfo);
+            }
+        }
+
+        let addr = addr.as_ref().map(|addr| addr.into_inner());
+
+        let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+
+        if let Some((ref addrp, len)) = addr {
+            msg.msg_name = addrp.as_ptr() as *mut libc::c_void;
+            msg.msg_namelen = len;
+        }
+
+        msg.msg_iov = bufs.as_ptr() as *mut libc::iovec;
+        msg.msg_iovlen = bufs.len() as _;
+        msg.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+        msg.msg_controllen = used as _;
+
+        let ret = cvt_r(|| unsafe { libc::sendmsg(self.0.raw(), &msg, 0) })?;
+
+        Ok(ret as usize)
+    }
+
+    /// `sctp_recvv` equivalent: scatter one read over `bufs`. The `RecvInfo` only carries
+    /// message details with `set_recv_rcvinfo`, and `NxtInfo` requires `set_recv_nxtinfo`.
+    /// A cut control buffer is reported by `RecvInfo::control_truncated`
+    #[allow(clippy::type_complexity)]
+    pub fn recvv(&self, bufs: &mut [IoSliceMut]) -> io::Result<(usize, RecvInfo, Option<NxtInfo>, Option<SocketAddr>, Option<Notification>)> {
+        let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+        let mut control = [0u64; CONTROL_LEN];
+
+        let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+        msg.msg_name = &mut storage as *mut libc::sockaddr_storage as *mut libc::c_void;
+        msg.msg_namelen = mem::size_of_val(&storage) as libc::socklen_t;
+        msg.msg_iov = bufs.as_mut_ptr() as *mut libc::iovec;
+        msg.msg_iovlen = bufs.len() as _;
+        msg.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+        msg.msg_controllen = mem::size_of_val(&control) as _;
+
+        let size = cvt_r(|| unsafe { libc::recvmsg(self.0.raw(), &mut msg, 0) })? as usize;
+        let flags = msg.msg_flags;
+
+        let mut rcvinfo: sys::sctp_rcvinfo = unsafe { mem::zeroed() };
+        let mut nxtinfo = None;
+
+        unsafe {
+            let mut cmsg = libc::CMSG_FIRSTHDR(&msg);
+
+            while !cmsg.is_null() {
+                if (*cmsg).cmsg_level == sys::IPPROTO_SCTP {
+                    let data = libc::CMSG_DATA(cmsg);
+
+                    if (*cmsg).cmsg_type == sys::sctp_cmsg_type::SCTP_RCVINFO as libc::c_int
+                        && (*cmsg).cmsg_len as usize >= libc::CMSG_LEN(mem::size_of::<sys::sctp_rcvinfo>() as libc::c_uint) as usize {
+                        rcvinfo = ptr::read_unaligned(data as *const sys::sctp_rcvinfo);
+                    } else if (*cmsg).cmsg_type == sys::sctp_cmsg_type::SCTP_NXTINFO as libc::c_int
+                        && (*cmsg).cmsg_len as usize >= libc::CMSG_LEN(mem::size_of::<sys::sctp_nxtinfo>() as libc::c_uint) as usize {
+                        let raw = ptr::read_unaligned(data as *const sys::sctp_nxtinfo);
+                        nxtinfo = Some(NxtInfo::from(raw));
+                    }
+                }
+
+                cmsg = libc::CMSG_NXTHDR(&msg, cmsg);
+            }
+        }
+
+        let addr = sockaddr_to_addr(&storage, msg.msg_namelen as usize).ok();
+
+        let notification = if flags & sys::MSG_NOTIFICATION == sys::MSG_NOTIFICATION && flags & libc::MSG_EOR == libc::MSG_EOR {
+            let mut buf = Vec::with_capacity(size);
+            for slice in bufs.iter() {
+                let remaining = size - buf.len();
+                buf.extend_from_slice(&slice[..cmp::min(slice.len(), remaining)]);
+            }
+            notification_parse(&buf)?
+        } else {
+            None
+        };
+
+        Ok((size, RecvInfo::from((rcvinfo, flags)), nxtinfo, addr, notification))
+    }
+
+    pub fn set_recv_rcvinfo(&self, on: bool) -> io::Result<()> {
+        self.setsockopt(sys::SOL_SCTP, sys::SCTP_RECVRCVINFO, on as libc::c_int)
+    }
+
+    pub fn recv_rcvinfo(&self) -> io::Result<bool> {
+        let raw: libc::c_int = self.getsockopt(sys::SOL_SCTP, sys::SCTP_RECVRCVINFO)?;
+        Ok(raw != 0)
+    }
+
+    pub fn set_recv_nxtinfo(&self, on: bool) -> io::Result<()> {
+        self.setsockopt(sys::SOL_SCTP, sys::SCTP_RECVNXTINFO, on as libc::c_int)
+    }
+
+    pub fn recv_nxtinfo(&self) -> io::Result<bool> {
+        let raw: libc::c_int = self.getsockopt(sys::SOL_SCTP, sys::SCTP_RECVNXTINFO)?;
+        Ok(raw != 0)
+    }
+
+    pub fn shutdown_assoc(&self, id: sys::sctp_assoc_t) -> io::Result<()> {
+        let mut info: sys::sctp_sndrcvinfo = unsafe { mem::zeroed() };
+        info.sinfo_flags = sys::sctp_sinfo_flags::SCTP_EOF as u16;
+        info.sinfo_assoc_id = id;
 
+        self.send(&[], &info)?;
+
+        Ok(())
+    }
+
+    pub fn abort_assoc(&self, id: sys::sctp_assoc_t, reason: &[u8]) -> io::Result<()> {
+        let mut info: sys::sctp_sndrcvinfo = unsafe { mem::zeroed() };
+        info.sinfo_flags = sys::sctp_sinfo_flags::SCTP_ABORT as u16;
+        info.sinfo_assoc_id = id;
+
+        self.send(reason, &info)?;
+
+        Ok(())
     }
 
     pub fn set_timeout(&self, dur: Option<Duration>, kind: libc::c_int) -> io::Result<()> {
@@ -310,14 +546,14 @@ impl Socket {
                                               "cannot set a 0 duration timeout"));
                 }
 
-                let secs = if dur.as_secs() > libc::time_t::max_value() as u64 {
-                    libc::time_t::max_value()
+                let secs = if dur.as_secs() > libc::time_t::MAX as u64 {
+                    libc::time_t::MAX
                 } else {
                     dur.as_secs() as libc::time_t
                 };
                 let mut timeout = libc::timeval {
                     tv_sec: secs,
-                    tv_usec: (dur.subsec_nanos() / 1000) as libc::suseconds_t,
+                    tv_usec: dur.subsec_micros() as libc::suseconds_t,
                 };
                 if timeout.tv_sec == 0 && timeout.tv_usec == 0 {
                     timeout.tv_usec = 1;
@@ -345,11 +581,25 @@ impl Socket {
         }
     }
 
-    pub fn shutdown(&self) -> io::Result<()> {
-        cvt(unsafe { libc::shutdown(self.0.raw(), libc::SHUT_RDWR) })?;
+    pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+        let how = match how {
+            Shutdown::Write => libc::SHUT_WR,
+            Shutdown::Read => libc::SHUT_RD,
+            Shutdown::Both => libc::SHUT_RDWR
+        };
+        cvt(unsafe { libc::shutdown(self.0.raw(), how) })?;
         Ok(())
     }
 
+    /// `SO_SNDBUF` or `SO_RCVBUF`, sizes that don't fit the kernel's `int` are refused
+    pub fn set_buffer_size(&self, opt: libc::c_int, size: usize) -> io::Result<()> {
+        if size > libc::c_int::MAX as usize {
+            return Err(Error::new(ErrorKind::InvalidInput, "Buffer size is too large"))
+        }
+
+        self.setsockopt(libc::SOL_SOCKET, opt, size as libc::c_int)
+    }
+
     pub fn set_nodelay(&self, nodelay: bool) -> io::Result<()> {
         self.setsockopt(sys::SOL_SCTP, sys::SCTP_NODELAY, nodelay as libc::c_int)
     }
@@ -359,6 +609,220 @@ impl Socket {
         Ok(raw != 0)
     }
 
+    pub fn set_auto_asconf(&self, auto_asconf: bool) -> io::Result<()> {
+        self.setsockopt(sys::SOL_SCTP, sys::SCTP_AUTO_ASCONF, auto_asconf as libc::c_int)
+    }
+
+    pub fn auto_asconf(&self) -> io::Result<bool> {
+        let raw: libc::c_int = self.getsockopt(sys::SOL_SCTP, sys::SCTP_AUTO_ASCONF)?;
+        Ok(raw != 0)
+    }
+
+    pub fn set_init_params(&self, params: &InitParams) -> io::Result<()> {
+        if params.get_max_init_timeo() > Duration::from_millis(u16::MAX as u64) {
+            return Err(Error::new(ErrorKind::InvalidInput, "INIT timeout is too long"))
+        }
+
+        self.setsockopt(sys::SOL_SCTP, sys::SCTP_INITMSG, sys::sctp_initmsg::from(params))
+    }
+
+    pub fn init_params(&self) -> io::Result<InitParams> {
+        let raw: sys::sctp_initmsg = self.getsockopt(sys::SOL_SCTP, sys::SCTP_INITMSG)?;
+        Ok(InitParams::from(raw))
+    }
+
+    pub fn set_rto_info(&self, id: sys::sctp_assoc_t, info: &RtoInfo) -> io::Result<()> {
+        self.setsockopt(sys::SOL_SCTP, sys::SCTP_RTOINFO, info.to_raw(id))
+    }
+
+    pub fn rto_info(&self, id: sys::sctp_assoc_t) -> io::Result<RtoInfo> {
+        let raw: sys::sctp_rtoinfo = self.sctp_opt_info(sys::SCTP_RTOINFO, id)?;
+        Ok(RtoInfo::from(raw))
+    }
+
+    pub fn set_assoc_params(&self, id: sys::sctp_assoc_t, params: &AssocParams) -> io::Result<()> {
+        self.setsockopt(sys::SOL_SCTP, sys::SCTP_ASSOCINFO, params.to_raw(id))
+    }
+
+    pub fn assoc_params(&self, id: sys::sctp_assoc_t) -> io::Result<AssocParams> {
+        let raw: sys::sctp_assocparams = self.sctp_opt_info(sys::SCTP_ASSOCINFO, id)?;
+        Ok(AssocParams::from(raw))
+    }
+
+    pub fn set_peer_addr_params(&self, id: sys::sctp_assoc_t, addr: Option<&SocketAddr>, params: &PeerAddrParams) -> io::Result<()> {
+        self.setsockopt(sys::SOL_SCTP, sys::SCTP_PEER_ADDR_PARAMS, params.to_raw(id, addr))
+    }
+
+    pub fn peer_addr_params(&self, id: sys::sctp_assoc_t, addr: Option<&SocketAddr>) -> io::Result<PeerAddrParams> {
+        let raw = PeerAddrParams::new().to_raw(id, addr);
+        let raw = self.getsockopt_with(sys::SOL_SCTP, sys::SCTP_PEER_ADDR_PARAMS, raw)?;
+        Ok(PeerAddrParams::from(raw))
+    }
+
+    pub fn status(&self, id: sys::sctp_assoc_t) -> io::Result<AssocStatus> {
+        let raw: sys::sctp_status = self.sctp_opt_info(sys::SCTP_STATUS, id)?;
+        Ok(AssocStatus::from(raw))
+    }
+
+    pub fn peer_addr_info(&self, id: sys::sctp_assoc_t, addr: &SocketAddr) -> io::Result<PathInfo> {
+        let mut raw: sys::sctp_paddrinfo = unsafe { mem::zeroed() };
+        raw.spinfo_assoc_id = id;
+        raw.spinfo_address = addr_to_storage(addr);
+
+        let raw = self.getsockopt_with(sys::SOL_SCTP, sys::SCTP_GET_PEER_ADDR_INFO, raw)?;
+        Ok(PathInfo::from(raw))
+    }
+
+    pub fn paths(&self, id: sys::sctp_assoc_t) -> io::Result<Vec<PathInfo>> {
+        let addrs = self.peer_addrs(id)?;
+        let mut paths = Vec::with_capacity(addrs.len());
+
+        for addr in &addrs {
+            paths.push(self.peer_addr_info(id, addr)?);
+        }
+
+        Ok(paths)
+    }
+
+    pub fn assoc_stats(&self, id: sys::sctp_assoc_t) -> io::Result<AssocStats> {
+        let mut raw: sys::sctp_assoc_stats = unsafe { mem::zeroed() };
+        raw.sas_assoc_id = id;
+
+        let raw = self.getsockopt_with(sys::SOL_SCTP, sys::SCTP_GET_ASSOC_STATS, raw)?;
+        Ok(AssocStats::from(raw))
+    }
+
+    pub fn set_primary_addr(&self, id: sys::sctp_assoc_t, addr: &SocketAddr) -> io::Result<()> {
+        let prim = sys::sctp_setprim {
+            ssp_assoc_id: id,
+            ssp_addr: addr_to_storage(addr)
+        };
+
+        self.setsockopt(sys::SOL_SCTP, sys::SCTP_PRIMARY_ADDR, prim)
+    }
+
+    pub fn primary_addr(&self, id: sys::sctp_assoc_t) -> io::Result<SocketAddr> {
+        let mut prim: sys::sctp_setprim = unsafe { mem::zeroed() };
+        prim.ssp_assoc_id = id;
+
+        let prim = self.getsockopt_with(sys::SOL_SCTP, sys::SCTP_PRIMARY_ADDR, prim)?;
+        let storage = prim.ssp_addr;
+
+        sockaddr_to_addr(&storage, mem::size_of_val(&storage))
+    }
+
+    pub fn set_peer_primary_addr(&self, id: sys::sctp_assoc_t, addr: &SocketAddr) -> io::Result<()> {
+        let prim = sys::sctp_setpeerprim {
+            sspp_assoc_id: id,
+            sspp_addr: addr_to_storage(addr)
+        };
+
+        self.setsockopt(sys::SOL_SCTP, sys::SCTP_SET_PEER_PRIMARY_ADDR, prim)
+    }
+
+    pub fn add_auth_chunk(&self, chunk: u8) -> io::Result<()> {
+        self.setsockopt(sys::SOL_SCTP, sys::SCTP_AUTH_CHUNK, sys::sctp_authchunk { sauth_chunk: chunk })
+    }
+
+    pub fn set_hmac_idents(&self, algos: &[HmacAlgo]) -> io::Result<()> {
+        let mut buf = Vec::with_capacity(mem::size_of::<u32>() + algos.len() * mem::size_of::<u16>());
+
+        buf.extend_from_slice(&(algos.len() as u32).to_ne_bytes());
+        for algo in algos {
+            buf.extend_from_slice(&algo.ident().to_ne_bytes());
+        }
+
+        self.setsockopt_bytes(sys::SOL_SCTP, sys::SCTP_HMAC_IDENT, &buf)
+    }
+
+    pub fn hmac_idents(&self) -> io::Result<Vec<HmacAlgo>> {
+        let mut buf = [0u8; 4 + 2 * 32];
+
+        let len = self.getsockopt_bytes(sys::SOL_SCTP, sys::SCTP_HMAC_IDENT, &mut buf)?;
+
+        if len < 4 {
+            return Err(Error::new(ErrorKind::InvalidData, "Invalid hmac ident list"))
+        }
+
+        let count = u32::from_ne_bytes([buf[0], buf[1], buf[2], buf[3]]) as usize;
+        let count = cmp::min(count, (len - 4) / 2);
+
+        let mut algos = Vec::with_capacity(count);
+        for i in 0..count {
+            let offset = 4 + i * 2;
+            algos.push(HmacAlgo::from(u16::from_ne_bytes([buf[offset], buf[offset + 1]])));
+        }
+
+        Ok(algos)
+    }
+
+    pub fn add_auth_key(&self, id: sys::sctp_assoc_t, keynumber: u16, key: &[u8]) -> io::Result<()> {
+        if key.len() > u16::MAX as usize {
+            return Err(Error::new(ErrorKind::InvalidInput, "Key is too long"))
+        }
+
+        let mut buf = Vec::with_capacity(8 + key.len());
+
+        buf.extend_from_slice(&id.to_ne_bytes());
+        buf.extend_from_slice(&keynumber.to_ne_bytes());
+        buf.extend_from_slice(&(key.len() as u16).to_ne_bytes());
+        buf.extend_from_slice(key);
+
+        self.setsockopt_bytes(sys::SOL_SCTP, sys::SCTP_AUTH_KEY, &buf)
+    }
+
+    pub fn set_active_auth_key(&self, id: sys::sctp_assoc_t, keynumber: u16) -> io::Result<()> {
+        let key = sys::sctp_authkeyid {
+            scact_assoc_id: id,
+            scact_keynumber: keynumber
+        };
+
+        self.setsockopt(sys::SOL_SCTP, sys::SCTP_AUTH_ACTIVE_KEY, key)
+    }
+
+    pub fn active_auth_key(&self, id: sys::sctp_assoc_t) -> io::Result<u16> {
+        let key = sys::sctp_authkeyid {
+            scact_assoc_id: id,
+            scact_keynumber: 0
+        };
+
+        let key = self.getsockopt_with(sys::SOL_SCTP, sys::SCTP_AUTH_ACTIVE_KEY, key)?;
+        Ok(key.scact_keynumber)
+    }
+
+    pub fn delete_auth_key(&self, id: sys::sctp_assoc_t, keynumber: u16) -> io::Result<()> {
+        let key = sys::sctp_authkeyid {
+            scact_assoc_id: id,
+            scact_keynumber: keynumber
+        };
+
+        self.setsockopt(sys::SOL_SCTP, sys::SCTP_AUTH_DELETE_KEY, key)
+    }
+
+    fn auth_chunks(&self, id: sys::sctp_assoc_t, opt: libc::c_int) -> io::Result<Vec<u8>> {
+        let mut buf = [0u8; 8 + 256];
+        buf[0..4].copy_from_slice(&id.to_ne_bytes());
+
+        let len = self.getsockopt_bytes(sys::SOL_SCTP, opt, &mut buf)?;
+
+        if len < 8 {
+            return Err(Error::new(ErrorKind::InvalidData, "Invalid auth chunk list"))
+        }
+
+        let count = u32::from_ne_bytes([buf[4], buf[5], buf[6], buf[7]]) as usize;
+        let count = cmp::min(count, len - 8);
+
+        Ok(buf[8..8 + count].to_vec())
+    }
+
+    pub fn peer_auth_chunks(&self, id: sys::sctp_assoc_t) -> io::Result<Vec<u8>> {
+        self.auth_chunks(id, sys::SCTP_PEER_AUTH_CHUNKS)
+    }
+
+    pub fn local_auth_chunks(&self, id: sys::sctp_assoc_t) -> io::Result<Vec<u8>> {
+        self.auth_chunks(id, sys::SCTP_LOCAL_AUTH_CHUNKS)
+    }
+
     pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
         let mut nonblocking = nonblocking as libc::c_int;
         cvt(unsafe { libc::ioctl(*self.as_inner(), libc::FIONBIO, &mut nonblocking) }).map(|_| ())
@@ -389,6 +853,20 @@ impl Socket {
         }
     }
 
+    pub fn setsockopt_bytes(&self, opt: libc::c_int, val: libc::c_int, payload: &[u8]) -> io::Result<()> {
+        unsafe {
+            cvt(libc::setsockopt(
+                *self.as_inner(),
+                opt,
+                val,
+                payload.as_ptr() as *const libc::c_void,
+                payload.len() as libc::socklen_t
+            ))?;
+
+            Ok(())
+        }
+    }
+
     pub fn getsockopt<T: Copy>(&self, opt: libc::c_int, val: libc::c_int) -> io::Result<T> {
         unsafe {
             let mut slot: T = mem::zeroed();
@@ -407,12 +885,46 @@ impl Socket {
         }
     }
 
+    /// Like `getsockopt`, for options that read their input (assoc id, address) from `slot`
+    pub fn getsockopt_with<T>(&self, opt: libc::c_int, val: libc::c_int, mut slot: T) -> io::Result<T> {
+        unsafe {
+            let mut len = mem::size_of::<T>() as libc::socklen_t;
+
+            cvt(libc::getsockopt(
+                *self.as_inner(),
+                opt,
+                val,
+                &mut slot as *mut T as *mut libc::c_void,
+                &mut len
+            ))?;
+
+            Ok(slot)
+        }
+    }
+
+    /// Variable length `getsockopt`, returns the length filled in by the kernel
+    pub fn getsockopt_bytes(&self, opt: libc::c_int, val: libc::c_int, buf: &mut [u8]) -> io::Result<usize> {
+        unsafe {
+            let mut len = buf.len() as libc::socklen_t;
+
+            cvt(libc::getsockopt(
+                *self.as_inner(),
+                opt,
+                val,
+                buf.as_mut_ptr() as *mut libc::c_void,
+                &mut len
+            ))?;
+
+            Ok(cmp::min(len as usize, buf.len()))
+        }
+    }
+
     pub fn sctp_opt_info<T>(&self, optname: libc::c_int, assoc: sys::sctp_assoc_t) -> io::Result<T> {
         unsafe {
             let mut val: T = mem::zeroed();
             let mut len = mem::size_of::<T>() as libc::socklen_t;
 
-            cvt(sys::sctp_opt_info(*self.as_inner(), assoc, optname, mem::transmute(&mut val), &mut len))?;
+            cvt(sys::sctp_opt_info(*self.as_inner(), assoc, optname, &mut val as *mut T as *mut libc::c_void, &mut len))?;
 
             Ok(val)
         }
@@ -428,52 +940,123 @@ impl Socket {
         Ok(())
     }
 
+    /// Subscribe to the given events for the socket and all of its associations, leaving the
+    /// others as they are
+    pub fn event_subscribe(&self, event: Event) -> io::Result<()> {
+        self.set_events(event, true)
+    }
+
+    #[deprecated(note = "renamed to `event_subscribe`")]
     pub fn event_subsctibe(&self, event: Event) -> io::Result<()> {
-        let mut subscribe: sys::sctp_event_subscribe = unsafe { mem::zeroed() };
+        self.event_subscribe(event)
+    }
 
-        if event.contains(Event::data_io()) {
-            subscribe.sctp_data_io_event = 1;
-        }
+    pub fn event_unsubscribe(&self, event: Event) -> io::Result<()> {
+        self.set_events(event, false)
+    }
 
-        if event.contains(Event::association()) {
-            subscribe.sctp_association_event = 1;
-        }
+    /// Events are switched one `SCTP_EVENT` call at a time. When one fails the ones already
+    /// switched are set back to their previous socket default, associations that differed from
+    /// the default end up with it.
+    fn set_events(&self, event: Event, on: bool) -> io::Result<()> {
+        let previous = self.subscribed_events()?;
+        let mut done = Vec::new();
 
-        if event.contains(Event::address()) {
-            subscribe.sctp_address_event = 1;
-        }
+        for (ev, sn_type) in event_types() {
+            if !event.contains(ev) {
+                continue
+            }
 
-        if event.contains(Event::send_failure()) {
-            subscribe.sctp_send_failure_event = 1;
-        }
+            let sn_type = sn_type as u16;
 
-        if event.contains(Event::peer_error()) {
-            subscribe.sctp_peer_error_event = 1;
-        }
+            match self.set_event(sn_type, on) {
+                // A kernel that doesn't know the type never delivers it either
+                Err(ref e) if !on && e.raw_os_error() == Some(libc::EINVAL) => (),
+                // Before Linux 5.0 there is only `SCTP_EVENTS`
+                Err(ref e) if e.raw_os_error() == Some(libc::ENOPROTOOPT) => {
+                    return self.set_events_legacy(event, on)
+                }
+                Err(e) => {
+                    for (ev, sn_type) in done {
+                        let _ = self.set_event(sn_type, previous.contains(ev));
+                    }
 
-        if event.contains(Event::shutdown()) {
-            subscribe.sctp_shutdown_event = 1;
+                    return Err(e)
+                }
+                Ok(()) => done.push((ev, sn_type))
+            }
         }
 
-        if event.contains(Event::partial_delivery()) {
-            subscribe.sctp_partial_delivery_event = 1;
-        }
+        Ok(())
+    }
 
-        if event.contains(Event::adaptation_layer()) {
-            subscribe.sctp_adaptation_layer_event = 1;
+    /// Switches the events of `legacy_events` in one read-modify-write of `SCTP_EVENTS`,
+    /// subscribing to any other event fails with `ENOPROTOOPT`
+    fn set_events_legacy(&self, event: Event, on: bool) -> io::Result<()> {
+        let mut subscribe: sys::sctp_event_subscribe = self.getsockopt(sys::IPPROTO_SCTP, sys::SCTP_EVENTS)?;
+        let mut rest = event;
+
+        for (ev, flag) in legacy_events(&mut subscribe) {
+            if event.contains(ev) {
+                *flag = on as u8;
+                rest.remove(ev);
+            }
         }
 
-        if event.contains(Event::authentication()) {
-            subscribe.sctp_authentication_event = 1;
+        if on && rest != Event::empty() {
+            return Err(Error::from_raw_os_error(libc::ENOPROTOOPT))
         }
 
-        if event.contains(Event::sender_dry()) {
-            subscribe.sctp_sender_dry_event = 1;
+        self.setsockopt(sys::IPPROTO_SCTP, sys::SCTP_EVENTS, subscribe)
+    }
+
+    fn set_event(&self, sn_type: u16, on: bool) -> io::Result<()> {
+        let param = sys::sctp_event {
+            se_assoc_id: sys::SCTP_ALL_ASSOC,
+            se_type: sn_type,
+            se_on: on as u8
+        };
+
+        self.setsockopt(sys::IPPROTO_SCTP, sys::SCTP_EVENT, param)
+    }
+
+    /// The events subscribed to for future associations
+    pub fn subscribed_events(&self) -> io::Result<Event> {
+        let mut event = Event::empty();
+
+        for (ev, sn_type) in event_types() {
+            let param = sys::sctp_event {
+                se_assoc_id: sys::SCTP_FUTURE_ASSOC,
+                se_type: sn_type as u16,
+                se_on: 0
+            };
+
+            let param = match self.getsockopt_with(sys::IPPROTO_SCTP, sys::SCTP_EVENT, param) {
+                Ok(param) => param,
+                Err(ref e) if e.raw_os_error() == Some(libc::EINVAL) => continue,
+                Err(ref e) if e.raw_os_error() == Some(libc::ENOPROTOOPT) => return self.subscribed_events_legacy(),
+                Err(e) => return Err(e)
+            };
+
+            if param.se_on != 0 {
+                event.insert(ev);
+            }
         }
 
-        self.setsockopt(sys::IPPROTO_SCTP, sys::SCTP_EVENTS, subscribe)?;
+        Ok(event)
+    }
 
-        Ok(())
+    fn subscribed_events_legacy(&self) -> io::Result<Event> {
+        let mut subscribe: sys::sctp_event_subscribe = self.getsockopt(sys::IPPROTO_SCTP, sys::SCTP_EVENTS)?;
+        let mut event = Event::empty();
+
+        for (ev, flag) in legacy_events(&mut subscribe) {
+            if *flag != 0 {
+                event.insert(ev);
+            }
+        }
+
+        Ok(event)
     }
 }
 
@@ -482,7 +1065,7 @@ impl AsInner<libc::c_int> for Socket {
 }
 
 impl FromInner<libc::c_int> for Socket {
-    fn from_inner(fd: libc::c_int) -> Socket { Socket(FileDesc::new(fd)) }
+    fn from_inner(fd: libc::c_int) -> Socket { Socket::from_inner(FileDesc::new(fd)) }
 }
 
 impl IntoInner<libc::c_int> for Socket {
@@ -497,6 +1080,12 @@ impl AsRawFd for Socket {
 
 impl FromRawFd for Socket {
     unsafe fn from_raw_fd(fd: RawFd) -> Socket {
-        Socket(FileDesc::new(fd))
+        Socket::from_inner(FileDesc::new(fd))
+    }
+}
+
+impl FromInner<FileDesc> for Socket {
+    fn from_inner(fd: FileDesc) -> Socket {
+        Socket(fd)
     }
 }
@@ -0,0 +1,315 @@
+use std::mem;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use sys;
+use sys::sctp_sstat_state::*;
+use sys::sctp_spinfo_state::*;
+
+use net::addr::sockaddr_to_addr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssocState {
+    Empty,
+    Closed,
+    CookieWait,
+    CookieEchoed,
+    Established,
+    ShutdownPending,
+    ShutdownSent,
+    ShutdownReceived,
+    ShutdownAckSent,
+    Unknown
+}
+
+impl From<i32> for AssocState {
+    fn from(state: i32) -> AssocState {
+        match state {
+            s if s == SCTP_EMPTY as i32 => AssocState::Empty,
+            s if s == SCTP_CLOSED as i32 => AssocState::Closed,
+            s if s == SCTP_COOKIE_WAIT as i32 => AssocState::CookieWait,
+            s if s == SCTP_COOKIE_ECHOED as i32 => AssocState::CookieEchoed,
+            s if s == SCTP_ESTABLISHED as i32 => AssocState::Established,
+            s if s == SCTP_SHUTDOWN_PENDING as i32 => AssocState::ShutdownPending,
+            s if s == SCTP_SHUTDOWN_SENT as i32 => AssocState::ShutdownSent,
+            s if s == SCTP_SHUTDOWN_RECEIVED as i32 => AssocState::ShutdownReceived,
+            s if s == SCTP_SHUTDOWN_ACK_SENT as i32 => AssocState::ShutdownAckSent,
+            _ => AssocState::Unknown
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathState {
+    Inactive,
+    PotentiallyFailed,
+    Active,
+    Unconfirmed,
+    Unknown
+}
+
+impl From<i32> for PathState {
+    fn from(state: i32) -> PathState {
+        match state {
+            s if s == SCTP_INACTIVE as i32 => PathState::Inactive,
+            s if s == SCTP_PF as i32 => PathState::PotentiallyFailed,
+            s if s == SCTP_ACTIVE as i32 => PathState::Active,
+            s if s == SCTP_UNCONFIRMED as i32 => PathState::Unconfirmed,
+            _ => PathState::Unknown
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PathInfo {
+    addr: Option<SocketAddr>,
+    state: PathState,
+    cwnd: u32,
+    srtt: Duration,
+    rto: Duration,
+    mtu: u32
+}
+
+impl PathInfo {
+    #[inline]
+    pub fn addr(&self) -> Option<SocketAddr> {
+        self.addr
+    }
+
+    #[inline]
+    pub fn state(&self) -> PathState {
+        self.state
+    }
+
+    #[inline]
+    pub fn cwnd(&self) -> u32 {
+        self.cwnd
+    }
+
+    #[inline]
+    pub fn srtt(&self) -> Duration {
+        self.srtt
+    }
+
+    #[inline]
+    pub fn rto(&self) -> Duration {
+        self.rto
+    }
+
+    #[inline]
+    pub fn mtu(&self) -> u32 {
+        self.mtu
+    }
+}
+
+impl From<sys::sctp_paddrinfo> for PathInfo {
+    fn from(info: sys::sctp_paddrinfo) -> PathInfo {
+        let storage = info.spinfo_address;
+
+        PathInfo {
+            addr: sockaddr_to_addr(&storage, mem::size_of_val(&storage)).ok(),
+            state: PathState::from(info.spinfo_state),
+            cwnd: info.spinfo_cwnd,
+            srtt: Duration::from_millis(info.spinfo_srtt as u64),
+            rto: Duration::from_millis(info.spinfo_rto as u64),
+            mtu: info.spinfo_mtu
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AssocStatus {
+    state: AssocState,
+    rwnd: u32,
+    unacked_data: u16,
+    pending_data: u16,
+    in_streams: u16,
+    out_streams: u16,
+    fragmentation_point: u32,
+    primary: PathInfo
+}
+
+impl AssocStatus {
+    #[inline]
+    pub fn state(&self) -> AssocState {
+        self.state
+    }
+
+    #[inline]
+    pub fn rwnd(&self) -> u32 {
+        self.rwnd
+    }
+
+    #[inline]
+    pub fn unacked_data(&self) -> u16 {
+        self.unacked_data
+    }
+
+    #[inline]
+    pub fn pending_data(&self) -> u16 {
+        self.pending_data
+    }
+
+    #[inline]
+    pub fn in_streams(&self) -> u16 {
+        self.in_streams
+    }
+
+    #[inline]
+    pub fn out_streams(&self) -> u16 {
+        self.out_streams
+    }
+
+    #[inline]
+    pub fn fragmentation_point(&self) -> u32 {
+        self.fragmentation_point
+    }
+
+    #[inline]
+    pub fn primary(&self) -> PathInfo {
+        self.primary
+    }
+}
+
+impl From<sys::sctp_status> for AssocStatus {
+    fn from(status: sys::sctp_status) -> AssocStatus {
+        AssocStatus {
+            state: AssocState::from(status.sstat_state),
+            rwnd: status.sstat_rwnd,
+            unacked_data: status.sstat_unackdata,
+            pending_data: status.sstat_penddata,
+            in_streams: status.sstat_instrms,
+            out_streams: status.sstat_outstrms,
+            fragmentation_point: status.sstat_fragmentation_point,
+            primary: PathInfo::from(status.sstat_primary)
+        }
+    }
+}
+
+/// Association statistics (`SCTP_GET_ASSOC_STATS`), the kernel resets the observed max RTO on
+/// every read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AssocStats {
+    max_rto: Duration,
+    max_rto_addr: Option<SocketAddr>,
+    in_sacks: u64,
+    out_sacks: u64,
+    in_packets: u64,
+    out_packets: u64,
+    retrans_chunks: u64,
+    out_of_seq_tsns: u64,
+    in_dup_chunks: u64,
+    gap_count: u64,
+    in_unordered_chunks: u64,
+    out_unordered_chunks: u64,
+    in_ordered_chunks: u64,
+    out_ordered_chunks: u64,
+    in_ctrl_chunks: u64,
+    out_ctrl_chunks: u64
+}
+
+impl AssocStats {
+    #[inline]
+    pub fn max_rto(&self) -> Duration {
+        self.max_rto
+    }
+
+    /// The peer address the max RTO was observed on
+    #[inline]
+    pub fn max_rto_addr(&self) -> Option<SocketAddr> {
+        self.max_rto_addr
+    }
+
+    #[inline]
+    pub fn in_sacks(&self) -> u64 {
+        self.in_sacks
+    }
+
+    #[inline]
+    pub fn out_sacks(&self) -> u64 {
+        self.out_sacks
+    }
+
+    #[inline]
+    pub fn in_packets(&self) -> u64 {
+        self.in_packets
+    }
+
+    #[inline]
+    pub fn out_packets(&self) -> u64 {
+        self.out_packets
+    }
+
+    #[inline]
+    pub fn retrans_chunks(&self) -> u64 {
+        self.retrans_chunks
+    }
+
+    #[inline]
+    pub fn out_of_seq_tsns(&self) -> u64 {
+        self.out_of_seq_tsns
+    }
+
+    #[inline]
+    pub fn in_dup_chunks(&self) -> u64 {
+        self.in_dup_chunks
+    }
+
+    #[inline]
+    pub fn gap_count(&self) -> u64 {
+        self.gap_count
+    }
+
+    #[inline]
+    pub fn in_unordered_chunks(&self) -> u64 {
+        self.in_unordered_chunks
+    }
+
+    #[inline]
+    pub fn out_unordered_chunks(&self) -> u64 {
+        self.out_unordered_chunks
+    }
+
+    #[inline]
+    pub fn in_ordered_chunks(&self) -> u64 {
+        self.in_ordered_chunks
+    }
+
+    #[inline]
+    pub fn out_ordered_chunks(&self) -> u64 {
+        self.out_ordered_chunks
+    }
+
+    #[inline]
+    pub fn in_ctrl_chunks(&self) -> u64 {
+        self.in_ctrl_chunks
+    }
+
+    #[inline]
+    pub fn out_ctrl_chunks(&self) -> u64 {
+        self.out_ctrl_chunks
+    }
+}
+
+impl From<sys::sctp_assoc_stats> for AssocStats {
+    fn from(stats: sys::sctp_assoc_stats) -> AssocStats {
+        AssocStats {
+            max_rto: Duration::from_millis(stats.sas_maxrto),
+            max_rto_addr: sockaddr_to_addr(&stats.sas_obs_rto_ipaddr, mem::size_of_val(&stats.sas_obs_rto_ipaddr)).ok(),
+            in_sacks: stats.sas_isacks,
+            out_sacks: stats.sas_osacks,
+            in_packets: stats.sas_ipackets,
+            out_packets: stats.sas_opackets,
+            retrans_chunks: stats.sas_rtxchunks,
+            out_of_seq_tsns: stats.sas_outofseqtsns,
+            in_dup_chunks: stats.sas_idupchunks,
+            gap_count: stats.sas_gapcnt,
+            in_unordered_chunks: stats.sas_iuodchunks,
+            out_unordered_chunks: stats.sas_ouodchunks,
+            in_ordered_chunks: stats.sas_iodchunks,
+            out_ordered_chunks: stats.sas_oodchunks,
+            in_ctrl_chunks: stats.sas_ictrlchunks,
+            out_ctrl_chunks: stats.sas_octrlchunks
+        }
+    }
+}
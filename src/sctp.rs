@@ -1,35 +1,108 @@
 use std::net::{ToSocketAddrs, SocketAddr, Shutdown};
-use std::io::{self, Error, ErrorKind, Read, Write};
+use std::io::{self, Error, ErrorKind, Read, Write, IoSlice, IoSliceMut};
 use std::mem;
 use std::time::Duration;
 use std::fmt;
-use std::os::unix::io::{AsRawFd, RawFd, FromRawFd};
+use std::os::unix::io::{AsRawFd, RawFd, FromRawFd, AsFd, BorrowedFd};
 
 use libc;
 
 use net::socket::Socket;
 use net::socket::BindOp;
 use net::addr::sockaddr_to_addr;
-use net::addr::parse_addr;
+use net::addr::{parse_addr, parse_addrs};
 use net::AsInner;
 use net::event::Event;
 use net::notification::Notification;
+use net::assoc::AssocId;
+use net::info::{RecvInfo, NxtInfo, SendOptions};
+use net::message::MessageBuf;
+use net::opt::{InitParams, RtoInfo, AssocParams, PeerAddrParams, HmacAlgo};
+use net::status::{AssocStatus, PathInfo, AssocStats};
+
+use sys;
+
+/// Listen backlog of the sockets not set up through `SctpSocketBuilder`
+const DEFAULT_BACKLOG: libc::c_int = 128;
 
 pub struct SctpStream(Socket);
 
 impl SctpStream {
     pub fn connect<A: ToSocketAddrs>(addr: A) -> io::Result<SctpStream> {
+        SctpStream::connect_inner(addr, None, false)
+    }
+
+    /// Connect with `params` applied before the association is set up
+    pub fn connect_with_init<A: ToSocketAddrs>(addr: A, params: &InitParams) -> io::Result<SctpStream> {
+        SctpStream::connect_inner(addr, Some(params), false)
+    }
+
+    /// Start connecting on a non-blocking socket. The association is up once the stream
+    /// turns writable, `take_error` then tells whether the setup failed
+    pub fn connect_nonblocking<A: ToSocketAddrs>(addr: A) -> io::Result<SctpStream> {
+        SctpStream::connect_inner(addr, None, true)
+    }
+
+    fn connect_inner<A: ToSocketAddrs>(addr: A, init: Option<&InitParams>, nonblocking: bool) -> io::Result<SctpStream> {
         let addr = parse_addr(addr)?;
 
         let sock = Socket::new(&addr, libc::SOCK_STREAM)?;
 
-        sock.connect(&addr)?;
+        if let Some(params) = init {
+            sock.set_init_params(params)?;
+        }
+
+        if nonblocking {
+            sock.set_nonblocking(true)?;
+        }
+
+        match sock.connect(&addr) {
+            Ok(()) => (),
+            Err(ref e) if nonblocking && e.raw_os_error() == Some(libc::EINPROGRESS) => (),
+            Err(e) => return Err(e)
+        }
 
         Ok(SctpStream(sock))
     }
 
-    pub fn connectx<A: ToSocketAddrs>(addrs: &[A]) -> io::Result<SctpStream>{
-        if addrs.len() == 0 {
+    pub fn connectx<A: ToSocketAddrs>(addrs: &[A]) -> io::Result<SctpStream> {
+        SctpStream::connectx_inner(addrs, None, false)
+    }
+
+    /// Connect with `params` applied before the association is set up
+    pub fn connectx_with_init<A: ToSocketAddrs>(addrs: &[A], params: &InitParams) -> io::Result<SctpStream> {
+        SctpStream::connectx_inner(addrs, Some(params), false)
+    }
+
+    /// Bind the local addresses `local` before connecting to `peers`, so both ends of the
+    /// association are multihomed
+    ///
+    /// ```no_run
+    /// use sctp::SctpStream;
+    ///
+    /// let stream = SctpStream::connectx_from(&["127.0.0.2:0", "127.0.0.3:0"], &["127.0.0.1:3868"]).unwrap();
+    /// println!("{:?}", stream.local_addrs());
+    /// ```
+    pub fn connectx_from<L: ToSocketAddrs, A: ToSocketAddrs>(local: &[L], peers: &[A]) -> io::Result<SctpStream> {
+        let local2 = parse_addrs(local)?;
+        let peers2 = parse_addrs(peers)?;
+
+        let builder = if local2.iter().chain(peers2.iter()).any(|addr| addr.is_ipv6()) {
+            SctpSocketBuilder::stream_v6()?
+        } else {
+            SctpSocketBuilder::stream_v4()?
+        };
+
+        builder.bindx(&local2)?.connectx(&peers2)
+    }
+
+    /// Multihomed `connect_nonblocking`
+    pub fn connectx_nonblocking<A: ToSocketAddrs>(addrs: &[A]) -> io::Result<SctpStream> {
+        SctpStream::connectx_inner(addrs, None, true)
+    }
+
+    fn connectx_inner<A: ToSocketAddrs>(addrs: &[A], init: Option<&InitParams>, nonblocking: bool) -> io::Result<SctpStream> {
+        if addrs.is_empty() {
             return Err(Error::new(ErrorKind::InvalidInput, "No addresses given"));
         }
 
@@ -48,24 +121,97 @@ impl SctpStream {
 
         let sock = Socket::new_raw(family, libc::SOCK_STREAM)?;
 
-        sock.connectx(&addrs2)?;
+        if let Some(params) = init {
+            sock.set_init_params(params)?;
+        }
+
+        if nonblocking {
+            sock.set_nonblocking(true)?;
+        }
+
+        match sock.connectx(&addrs2) {
+            Ok(_) => (),
+            Err(ref e) if nonblocking && e.raw_os_error() == Some(libc::EINPROGRESS) => (),
+            Err(e) => return Err(e)
+        }
 
         Ok(SctpStream(sock))
     }
 
+    /// Send one message, on `WouldBlock` nothing of it was queued
     pub fn sendmsg(&self, msg: &[u8], stream: u16) -> io::Result<usize> {
-        self.0.sendmsg(msg, None, stream, 0)
+        self.0.sendmsg(msg, None, &SendOptions::new().stream(stream))
+    }
+
+    pub fn sendmsg_with(&self, msg: &[u8], options: &SendOptions) -> io::Result<usize> {
+        self.0.sendmsg(msg, None, options)
+    }
+
+    pub fn recvmsg(&self, msg: &mut [u8]) -> io::Result<(usize, RecvInfo, Option<Notification>)> {
+        let (size, info, _, notification) = self.0.recvmsg(msg)?;
+        Ok((size, info, notification))
+    }
+
+    /// Receive a whole message, `msg` is grown as needed. After `WouldBlock` call again with
+    /// the same `msg` to continue the message
+    pub fn recv_message(&self, msg: &mut MessageBuf) -> io::Result<(usize, RecvInfo, Option<Notification>)> {
+        let (size, info, _, notification) = self.0.recv_message(msg)?;
+        Ok((size, info, notification))
+    }
+
+    /// Send one message gathered from `bufs`, see `SendOptions::pr_policy` and `SendOptions::auth_key`
+    pub fn sendv(&self, bufs: &[IoSlice], options: &SendOptions) -> io::Result<usize> {
+        self.0.sendv(bufs, None, 0, options)
+    }
+
+    /// Receive into `bufs`. `RecvInfo` is only filled in with `set_recv_rcvinfo`, and the
+    /// next message is only described with `set_recv_nxtinfo`
+    pub fn recvv(&self, bufs: &mut [IoSliceMut]) -> io::Result<(usize, RecvInfo, Option<NxtInfo>, Option<Notification>)> {
+        let (size, info, nxtinfo, _, notification) = self.0.recvv(bufs)?;
+        Ok((size, info, nxtinfo, notification))
+    }
+
+    pub fn set_recv_rcvinfo(&self, on: bool) -> io::Result<()> {
+        self.0.set_recv_rcvinfo(on)
+    }
+
+    pub fn recv_rcvinfo(&self) -> io::Result<bool> {
+        self.0.recv_rcvinfo()
+    }
+
+    pub fn set_recv_nxtinfo(&self, on: bool) -> io::Result<()> {
+        self.0.set_recv_nxtinfo(on)
     }
 
-    pub fn recvmsg(&self, msg: &mut [u8]) -> io::Result<(usize, u16, Option<Notification>)> {
-        let (size, stream, _, notification) = self.0.recvmsg(msg)?;
-        return Ok((size, stream, notification))
+    pub fn recv_nxtinfo(&self) -> io::Result<bool> {
+        self.0.recv_nxtinfo()
     }
 
     pub fn local_addrs(&self) -> io::Result<Vec<SocketAddr>> {
         self.0.local_addrs(0)
     }
 
+    /// Bind further local addresses, existing associations learn about them through ASCONF
+    /// (RFC 5061) and the peer reports `Notification::PaddrChange` when subscribed to `Event::address`
+    pub fn add_local_addrs<A: ToSocketAddrs>(&self, addrs: &[A]) -> io::Result<()> {
+        self.0.bindx(&parse_addrs(addrs)?, BindOp::AddAddr)
+    }
+
+    /// Unbind local addresses, withdrawing them from existing associations through ASCONF
+    pub fn remove_local_addrs<A: ToSocketAddrs>(&self, addrs: &[A]) -> io::Result<()> {
+        self.0.bindx(&parse_addrs(addrs)?, BindOp::RemAddr)
+    }
+
+    /// Follow the host's address changes automatically (`SCTP_AUTO_ASCONF`), only for
+    /// sockets bound to the wildcard address
+    pub fn set_auto_asconf(&self, auto_asconf: bool) -> io::Result<()> {
+        self.0.set_auto_asconf(auto_asconf)
+    }
+
+    pub fn auto_asconf(&self) -> io::Result<bool> {
+        self.0.auto_asconf()
+    }
+
     pub fn peer_addrs(&self) -> io::Result<Vec<SocketAddr>> {
         self.0.peer_addrs(0)
     }
@@ -95,7 +241,7 @@ impl SctpStream {
     }
 
     pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
-        self.0.setsockopt(libc::SOL_SOCKET, libc::SO_SNDBUF, &(size as libc::c_int))
+        self.0.set_buffer_size(libc::SO_SNDBUF, size)
     }
 
     pub fn get_send_buffer_size(&self) -> io::Result<usize> {
@@ -104,7 +250,7 @@ impl SctpStream {
     }
 
     pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
-        self.0.setsockopt(libc::SOL_SOCKET, libc::SO_RCVBUF, &(size as libc::c_int))
+        self.0.set_buffer_size(libc::SO_RCVBUF, size)
     }
 
     pub fn get_recv_buffer_size(&self) -> io::Result<usize> {
@@ -121,6 +267,110 @@ impl SctpStream {
         Ok(raw as u32)
     }
 
+    pub fn set_init_params(&self, params: &InitParams) -> io::Result<()> {
+        self.0.set_init_params(params)
+    }
+
+    pub fn init_params(&self) -> io::Result<InitParams> {
+        self.0.init_params()
+    }
+
+    pub fn set_rto_info(&self, info: &RtoInfo) -> io::Result<()> {
+        self.0.set_rto_info(0, info)
+    }
+
+    pub fn rto_info(&self) -> io::Result<RtoInfo> {
+        self.0.rto_info(0)
+    }
+
+    pub fn set_assoc_params(&self, params: &AssocParams) -> io::Result<()> {
+        self.0.set_assoc_params(0, params)
+    }
+
+    pub fn assoc_params(&self) -> io::Result<AssocParams> {
+        self.0.assoc_params(0)
+    }
+
+    /// Set the parameters of one peer address, or of every path of the association with `None`
+    pub fn set_peer_addr_params(&self, addr: Option<SocketAddr>, params: &PeerAddrParams) -> io::Result<()> {
+        self.0.set_peer_addr_params(0, addr.as_ref(), params)
+    }
+
+    pub fn peer_addr_params(&self, addr: Option<SocketAddr>) -> io::Result<PeerAddrParams> {
+        self.0.peer_addr_params(0, addr.as_ref())
+    }
+
+    pub fn status(&self) -> io::Result<AssocStatus> {
+        self.0.status(0)
+    }
+
+    pub fn peer_addr_info(&self, addr: SocketAddr) -> io::Result<PathInfo> {
+        self.0.peer_addr_info(0, &addr)
+    }
+
+    /// Path info of every peer address
+    pub fn paths(&self) -> io::Result<Vec<PathInfo>> {
+        self.0.paths(0)
+    }
+
+    pub fn assoc_stats(&self) -> io::Result<AssocStats> {
+        self.0.assoc_stats(0)
+    }
+
+    /// Select the peer address data is sent to by default
+    pub fn set_primary_addr(&self, addr: SocketAddr) -> io::Result<()> {
+        self.0.set_primary_addr(0, &addr)
+    }
+
+    pub fn primary_addr(&self) -> io::Result<SocketAddr> {
+        self.0.primary_addr(0)
+    }
+
+    /// Ask the peer to use one of our local addresses as its primary
+    pub fn set_peer_primary_addr(&self, addr: SocketAddr) -> io::Result<()> {
+        self.0.set_peer_primary_addr(0, &addr)
+    }
+
+    /// Require peers to authenticate chunks of type `chunk`, needs `net.sctp.auth_enable`
+    pub fn add_auth_chunk(&self, chunk: u8) -> io::Result<()> {
+        self.0.add_auth_chunk(chunk)
+    }
+
+    /// HMAC algorithms in order of preference, SHA-1 must be included
+    pub fn set_hmac_idents(&self, algos: &[HmacAlgo]) -> io::Result<()> {
+        self.0.set_hmac_idents(algos)
+    }
+
+    pub fn hmac_idents(&self) -> io::Result<Vec<HmacAlgo>> {
+        self.0.hmac_idents()
+    }
+
+    pub fn add_auth_key(&self, keynumber: u16, key: &[u8]) -> io::Result<()> {
+        self.0.add_auth_key(0, keynumber, key)
+    }
+
+    pub fn set_active_auth_key(&self, keynumber: u16) -> io::Result<()> {
+        self.0.set_active_auth_key(0, keynumber)
+    }
+
+    pub fn active_auth_key(&self) -> io::Result<u16> {
+        self.0.active_auth_key(0)
+    }
+
+    pub fn delete_auth_key(&self, keynumber: u16) -> io::Result<()> {
+        self.0.delete_auth_key(0, keynumber)
+    }
+
+    /// Chunk types the peer requires us to authenticate
+    pub fn peer_auth_chunks(&self) -> io::Result<Vec<u8>> {
+        self.0.peer_auth_chunks(0)
+    }
+
+    /// Chunk types we require the peer to authenticate
+    pub fn local_auth_chunks(&self) -> io::Result<Vec<u8>> {
+        self.0.local_auth_chunks(0)
+    }
+
     pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
         self.0.set_nonblocking(nonblocking)
     }
@@ -129,14 +379,30 @@ impl SctpStream {
         self.0.peek(buf)
     }
 
-    pub fn shutdown(&self, _: Shutdown) -> io::Result<()> {
-        self.0.shutdown()
+    pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+        self.0.shutdown(how)
     }
 
+    /// Subscribe to the given events, the current subscriptions stay in place. When it fails
+    /// the events already switched are set back.
+    ///
+    /// Events are switched with `SCTP_EVENT`, which needs Linux 5.0. Older kernels fall back to
+    /// `SCTP_EVENTS`, which only knows the events up to `Event::sender_dry`; subscribing to
+    /// the later ones fails there with `ENOPROTOOPT`.
     pub fn event_subscribe(&self, event: Event) -> io::Result<()> {
         self.0.event_subscribe(event)
     }
 
+    /// Stop the given notifications, kernels before 5.0 as for `event_subscribe`
+    pub fn event_unsubscribe(&self, event: Event) -> io::Result<()> {
+        self.0.event_unsubscribe(event)
+    }
+
+    /// The events subscribed to, kernels before 5.0 only report those up to `Event::sender_dry`
+    pub fn subscribed_events(&self) -> io::Result<Event> {
+        self.0.subscribed_events()
+    }
+
     pub fn try_clone(&self) -> io::Result<SctpStream> {
         Ok(SctpStream(self.0.duplicate()?))
     }
@@ -162,13 +428,13 @@ impl Write for SctpStream {
     }
 }
 
-impl<'a> Read for &'a SctpStream {
+impl Read for &SctpStream {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         self.0.read(buf)
     }
 }
 
-impl<'a> Write for &'a SctpStream {
+impl Write for &SctpStream {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
         self.0.write(buf)
     }
@@ -200,6 +466,12 @@ impl AsRawFd for SctpStream {
     }
 }
 
+impl AsFd for SctpStream {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        unsafe { BorrowedFd::borrow_raw(self.as_raw_fd()) }
+    }
+}
+
 impl FromRawFd for SctpStream {
     unsafe fn from_raw_fd(fd: RawFd) -> SctpStream {
         let sock = Socket::from_raw_fd(fd);
@@ -211,21 +483,43 @@ pub struct SctpListener(Socket);
 
 impl SctpListener {
     pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<SctpListener> {
+        SctpListener::bind_inner(addr, None)
+    }
+
+    /// Bind with `params` applied before any association is accepted
+    pub fn bind_with_init<A: ToSocketAddrs>(addr: A, params: &InitParams) -> io::Result<SctpListener> {
+        SctpListener::bind_inner(addr, Some(params))
+    }
+
+    fn bind_inner<A: ToSocketAddrs>(addr: A, init: Option<&InitParams>) -> io::Result<SctpListener> {
         let addr = parse_addr(addr)?;
 
         let sock = Socket::new(&addr, libc::SOCK_STREAM)?;
 
         sock.setsockopt(libc::SOL_SOCKET, libc::SO_REUSEADDR, 1 as libc::c_int)?;
 
+        if let Some(params) = init {
+            sock.set_init_params(params)?;
+        }
+
         sock.bind(&addr)?;
 
-        sock.listen(128)?;
+        sock.listen(DEFAULT_BACKLOG)?;
 
         Ok(SctpListener(sock))
     }
 
     pub fn bindx<A: ToSocketAddrs>(addrs: &[A]) -> io::Result<SctpListener> {
-        if addrs.len() == 0 {
+        SctpListener::bindx_inner(addrs, None)
+    }
+
+    /// Bind with `params` applied before any association is accepted
+    pub fn bindx_with_init<A: ToSocketAddrs>(addrs: &[A], params: &InitParams) -> io::Result<SctpListener> {
+        SctpListener::bindx_inner(addrs, Some(params))
+    }
+
+    fn bindx_inner<A: ToSocketAddrs>(addrs: &[A], init: Option<&InitParams>) -> io::Result<SctpListener> {
+        if addrs.is_empty() {
             return Err(Error::new(ErrorKind::InvalidInput, "No addresses given"));
         }
 
@@ -246,9 +540,13 @@ impl SctpListener {
 
         sock.setsockopt(libc::SOL_SOCKET, libc::SO_REUSEADDR, 1 as libc::c_int)?;
 
+        if let Some(params) = init {
+            sock.set_init_params(params)?;
+        }
+
         sock.bindx(&addrs2, BindOp::AddAddr)?;
 
-        sock.listen(128)?;
+        sock.listen(DEFAULT_BACKLOG)?;
 
         Ok(SctpListener(sock))
     }
@@ -257,7 +555,7 @@ impl SctpListener {
         let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
         let mut len = mem::size_of_val(&storage) as libc::socklen_t;
 
-        let sock = self.0.accept(&mut storage as *mut libc::sockaddr_storage as *mut libc::sockaddr, &mut len)?;
+        let sock = self.0.accept(&mut storage, &mut len)?;
         let addr = sockaddr_to_addr(&storage, len as usize)?;
 
         Ok((SctpStream(sock), addr))
@@ -267,6 +565,25 @@ impl SctpListener {
         self.0.local_addrs(0)
     }
 
+    /// Like `SctpStream::add_local_addrs`
+    pub fn add_local_addrs<A: ToSocketAddrs>(&self, addrs: &[A]) -> io::Result<()> {
+        self.0.bindx(&parse_addrs(addrs)?, BindOp::AddAddr)
+    }
+
+    /// Like `SctpStream::remove_local_addrs`
+    pub fn remove_local_addrs<A: ToSocketAddrs>(&self, addrs: &[A]) -> io::Result<()> {
+        self.0.bindx(&parse_addrs(addrs)?, BindOp::RemAddr)
+    }
+
+    /// Like `SctpStream::set_auto_asconf`
+    pub fn set_auto_asconf(&self, auto_asconf: bool) -> io::Result<()> {
+        self.0.set_auto_asconf(auto_asconf)
+    }
+
+    pub fn auto_asconf(&self) -> io::Result<bool> {
+        self.0.auto_asconf()
+    }
+
     pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
         self.0.setsockopt(libc::IPPROTO_IP, libc::IP_TTL, ttl as libc::c_int)
     }
@@ -276,12 +593,80 @@ impl SctpListener {
         Ok(raw as u32)
     }
 
+    pub fn set_init_params(&self, params: &InitParams) -> io::Result<()> {
+        self.0.set_init_params(params)
+    }
+
+    pub fn init_params(&self) -> io::Result<InitParams> {
+        self.0.init_params()
+    }
+
+    /// Require peers to authenticate chunks of type `chunk`, needs `net.sctp.auth_enable`
+    pub fn add_auth_chunk(&self, chunk: u8) -> io::Result<()> {
+        self.0.add_auth_chunk(chunk)
+    }
+
+    /// HMAC algorithms in order of preference, SHA-1 must be included
+    pub fn set_hmac_idents(&self, algos: &[HmacAlgo]) -> io::Result<()> {
+        self.0.set_hmac_idents(algos)
+    }
+
+    pub fn hmac_idents(&self) -> io::Result<Vec<HmacAlgo>> {
+        self.0.hmac_idents()
+    }
+
+    /// Add a shared key used by every association accepted afterwards
+    pub fn add_auth_key(&self, keynumber: u16, key: &[u8]) -> io::Result<()> {
+        self.0.add_auth_key(sys::SCTP_FUTURE_ASSOC, keynumber, key)
+    }
+
+    pub fn set_active_auth_key(&self, keynumber: u16) -> io::Result<()> {
+        self.0.set_active_auth_key(sys::SCTP_FUTURE_ASSOC, keynumber)
+    }
+
+    pub fn active_auth_key(&self) -> io::Result<u16> {
+        self.0.active_auth_key(sys::SCTP_FUTURE_ASSOC)
+    }
+
+    pub fn delete_auth_key(&self, keynumber: u16) -> io::Result<()> {
+        self.0.delete_auth_key(sys::SCTP_FUTURE_ASSOC, keynumber)
+    }
+
+    /// Chunk types we require peers to authenticate
+    pub fn local_auth_chunks(&self) -> io::Result<Vec<u8>> {
+        self.0.local_auth_chunks(sys::SCTP_FUTURE_ASSOC)
+    }
+
+    pub fn set_rto_info(&self, info: &RtoInfo) -> io::Result<()> {
+        self.0.set_rto_info(0, info)
+    }
+
+    pub fn rto_info(&self) -> io::Result<RtoInfo> {
+        self.0.rto_info(0)
+    }
+
     pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
         self.0.set_nonblocking(nonblocking)
     }
 
+    /// Like `SctpStream::event_subscribe`
+    pub fn event_subscribe(&self, event: Event) -> io::Result<()> {
+        self.0.event_subscribe(event)
+    }
+
+    #[deprecated(note = "renamed to `event_subscribe`")]
     pub fn event_subsctibe(&self, event: Event) -> io::Result<()> {
-        self.0.event_subsctibe(event)
+        self.event_subscribe(event)
+    }
+
+    /// Like `SctpStream::event_unsubscribe`
+    pub fn event_unsubscribe(&self, event: Event) -> io::Result<()> {
+        self.0.event_unsubscribe(event)
+    }
+
+    /// Like `SctpStream::subscribed_events`
+    pub fn subscribed_events(&self) -> io::Result<Event> {
+        self.0.subscribed_events()
     }
 
     pub fn try_clone(&self) -> io::Result<SctpListener> {
@@ -299,6 +684,12 @@ impl AsRawFd for SctpListener {
     }
 }
 
+impl AsFd for SctpListener {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        unsafe { BorrowedFd::borrow_raw(self.as_raw_fd()) }
+    }
+}
+
 impl FromRawFd for SctpListener {
     unsafe fn from_raw_fd(fd: RawFd) -> SctpListener {
         let sock = Socket::from_raw_fd(fd);
@@ -310,21 +701,43 @@ pub struct SctpEndpoint(Socket);
 
 impl SctpEndpoint {
     pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<SctpEndpoint> {
+        SctpEndpoint::bind_inner(addr, None)
+    }
+
+    /// Bind with `params` applied before any association is accepted
+    pub fn bind_with_init<A: ToSocketAddrs>(addr: A, params: &InitParams) -> io::Result<SctpEndpoint> {
+        SctpEndpoint::bind_inner(addr, Some(params))
+    }
+
+    fn bind_inner<A: ToSocketAddrs>(addr: A, init: Option<&InitParams>) -> io::Result<SctpEndpoint> {
         let addr = parse_addr(addr)?;
 
         let sock = Socket::new(&addr, libc::SOCK_SEQPACKET)?;
 
         sock.setsockopt(libc::SOL_SOCKET, libc::SO_REUSEADDR, 1 as libc::c_int)?;
 
+        if let Some(params) = init {
+            sock.set_init_params(params)?;
+        }
+
         sock.bind(&addr)?;
 
-        sock.listen(128)?;
+        sock.listen(DEFAULT_BACKLOG)?;
 
         Ok(SctpEndpoint(sock))
     }
 
     pub fn bindx<A: ToSocketAddrs>(addrs: &[A]) -> io::Result<SctpEndpoint> {
-        if addrs.len() == 0 {
+        SctpEndpoint::bindx_inner(addrs, None)
+    }
+
+    /// Bind with `params` applied before any association is accepted
+    pub fn bindx_with_init<A: ToSocketAddrs>(addrs: &[A], params: &InitParams) -> io::Result<SctpEndpoint> {
+        SctpEndpoint::bindx_inner(addrs, Some(params))
+    }
+
+    fn bindx_inner<A: ToSocketAddrs>(addrs: &[A], init: Option<&InitParams>) -> io::Result<SctpEndpoint> {
+        if addrs.is_empty() {
             return Err(Error::new(ErrorKind::InvalidInput, "No addresses given"));
         }
 
@@ -345,9 +758,13 @@ impl SctpEndpoint {
 
         sock.setsockopt(libc::SOL_SOCKET, libc::SO_REUSEADDR, 1 as libc::c_int)?;
 
+        if let Some(params) = init {
+            sock.set_init_params(params)?;
+        }
+
         sock.bindx(&addrs2, BindOp::AddAddr)?;
 
-        sock.listen(128)?;
+        sock.listen(DEFAULT_BACKLOG)?;
 
         Ok(SctpEndpoint(sock))
     }
@@ -368,20 +785,104 @@ impl SctpEndpoint {
         Ok(SctpEndpoint(sock))
     }
 
-    pub fn revc_from(&self, msg: &mut [u8]) -> io::Result<(usize, u16, Option<SocketAddr>, Option<Notification>)> {
+    pub fn revc_from(&self, msg: &mut [u8]) -> io::Result<(usize, RecvInfo, Option<SocketAddr>, Option<Notification>)> {
         self.0.recvmsg(msg)
     }
 
+    /// Receive a whole message, `msg` is grown as needed. After `WouldBlock` call again with
+    /// the same `msg` to continue the message
+    pub fn recv_message(&self, msg: &mut MessageBuf) -> io::Result<(usize, RecvInfo, Option<SocketAddr>, Option<Notification>)> {
+        self.0.recv_message(msg)
+    }
+
+    /// Send one message to `addr`, on `WouldBlock` nothing of it was queued
     pub fn send_to<A: ToSocketAddrs>(&self, msg: &[u8], addr: A, stream: u16) -> io:: Result<usize> {
         let addr = parse_addr(addr)?;
 
-        self.0.sendmsg(msg, Some(addr), stream, 0)
+        self.0.sendmsg(msg, Some(addr), &SendOptions::new().stream(stream))
+    }
+
+    pub fn send_to_with<A: ToSocketAddrs>(&self, msg: &[u8], addr: A, options: &SendOptions) -> io::Result<usize> {
+        let addr = parse_addr(addr)?;
+
+        self.0.sendmsg(msg, Some(addr), options)
+    }
+
+    /// Send one message gathered from `bufs` to `addr`, setting up an association if needed
+    pub fn sendv_to<A: ToSocketAddrs>(&self, bufs: &[IoSlice], addr: A, options: &SendOptions) -> io::Result<usize> {
+        let addr = parse_addr(addr)?;
+
+        self.0.sendv(bufs, Some(addr), 0, options)
+    }
+
+    /// Send one message gathered from `bufs` on an existing association
+    pub fn sendv_assoc(&self, bufs: &[IoSlice], assoc: AssocId, options: &SendOptions) -> io::Result<usize> {
+        self.0.sendv(bufs, None, assoc.as_i32(), options)
+    }
+
+    /// Receive into `bufs`. `RecvInfo` is only filled in with `set_recv_rcvinfo`, and the
+    /// next message is only described with `set_recv_nxtinfo`
+    #[allow(clippy::type_complexity)]
+    pub fn recvv(&self, bufs: &mut [IoSliceMut]) -> io::Result<(usize, RecvInfo, Option<NxtInfo>, Option<SocketAddr>, Option<Notification>)> {
+        self.0.recvv(bufs)
+    }
+
+    pub fn set_recv_rcvinfo(&self, on: bool) -> io::Result<()> {
+        self.0.set_recv_rcvinfo(on)
+    }
+
+    pub fn recv_rcvinfo(&self) -> io::Result<bool> {
+        self.0.recv_rcvinfo()
+    }
+
+    pub fn set_recv_nxtinfo(&self, on: bool) -> io::Result<()> {
+        self.0.set_recv_nxtinfo(on)
+    }
+
+    pub fn recv_nxtinfo(&self) -> io::Result<bool> {
+        self.0.recv_nxtinfo()
+    }
+
+    pub fn shutdown_assoc(&self, assoc: AssocId) -> io::Result<()> {
+        self.0.shutdown_assoc(assoc.as_i32())
+    }
+
+    /// Abort a single association, `reason` is sent to the peer as the user-initiated abort cause
+    pub fn abort_assoc(&self, assoc: AssocId, reason: &[u8]) -> io::Result<()> {
+        self.0.abort_assoc(assoc.as_i32(), reason)
+    }
+
+    pub fn peeloff(&self, assoc: AssocId) -> io::Result<SctpStream> {
+        Ok(SctpStream(self.0.peeloff(assoc.as_i32())?))
     }
 
     pub fn local_addrs(&self) -> io::Result<Vec<SocketAddr>> {
         self.0.local_addrs(0)
     }
 
+    /// Like `SctpStream::add_local_addrs`
+    pub fn add_local_addrs<A: ToSocketAddrs>(&self, addrs: &[A]) -> io::Result<()> {
+        self.0.bindx(&parse_addrs(addrs)?, BindOp::AddAddr)
+    }
+
+    /// Like `SctpStream::remove_local_addrs`
+    pub fn remove_local_addrs<A: ToSocketAddrs>(&self, addrs: &[A]) -> io::Result<()> {
+        self.0.bindx(&parse_addrs(addrs)?, BindOp::RemAddr)
+    }
+
+    /// Like `SctpStream::set_auto_asconf`
+    pub fn set_auto_asconf(&self, auto_asconf: bool) -> io::Result<()> {
+        self.0.set_auto_asconf(auto_asconf)
+    }
+
+    pub fn auto_asconf(&self) -> io::Result<bool> {
+        self.0.auto_asconf()
+    }
+
+    pub fn peer_addrs(&self, assoc: AssocId) -> io::Result<Vec<SocketAddr>> {
+        self.0.peer_addrs(assoc.as_i32())
+    }
+
     pub fn set_nodelay(&self, nodelay: bool) -> io::Result<()> {
         self.0.set_nodelay(nodelay)
     }
@@ -391,7 +892,7 @@ impl SctpEndpoint {
     }
 
     pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
-        self.0.setsockopt(libc::SOL_SOCKET, libc::SO_SNDBUF, &(size as libc::c_int))
+        self.0.set_buffer_size(libc::SO_SNDBUF, size)
     }
 
     pub fn get_send_buffer_size(&self) -> io::Result<usize> {
@@ -400,7 +901,7 @@ impl SctpEndpoint {
     }
 
     pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
-        self.0.setsockopt(libc::SOL_SOCKET, libc::SO_RCVBUF, &(size as libc::c_int))
+        self.0.set_buffer_size(libc::SO_RCVBUF, size)
     }
 
     pub fn get_recv_buffer_size(&self) -> io::Result<usize> {
@@ -408,6 +909,112 @@ impl SctpEndpoint {
         Ok(raw as usize)
     }
 
+    pub fn set_init_params(&self, params: &InitParams) -> io::Result<()> {
+        self.0.set_init_params(params)
+    }
+
+    pub fn init_params(&self) -> io::Result<InitParams> {
+        self.0.init_params()
+    }
+
+    /// Set the RTO parameters of `assoc`, or of the whole endpoint with `AssocId::future()`
+    pub fn set_rto_info(&self, assoc: AssocId, info: &RtoInfo) -> io::Result<()> {
+        self.0.set_rto_info(assoc.as_i32(), info)
+    }
+
+    pub fn rto_info(&self, assoc: AssocId) -> io::Result<RtoInfo> {
+        self.0.rto_info(assoc.as_i32())
+    }
+
+    pub fn set_assoc_params(&self, assoc: AssocId, params: &AssocParams) -> io::Result<()> {
+        self.0.set_assoc_params(assoc.as_i32(), params)
+    }
+
+    pub fn assoc_params(&self, assoc: AssocId) -> io::Result<AssocParams> {
+        self.0.assoc_params(assoc.as_i32())
+    }
+
+    /// Set the parameters of one peer address of `assoc`, or of every path of `assoc` with `None`
+    pub fn set_peer_addr_params(&self, assoc: AssocId, addr: Option<SocketAddr>, params: &PeerAddrParams) -> io::Result<()> {
+        self.0.set_peer_addr_params(assoc.as_i32(), addr.as_ref(), params)
+    }
+
+    pub fn peer_addr_params(&self, assoc: AssocId, addr: Option<SocketAddr>) -> io::Result<PeerAddrParams> {
+        self.0.peer_addr_params(assoc.as_i32(), addr.as_ref())
+    }
+
+    pub fn status(&self, assoc: AssocId) -> io::Result<AssocStatus> {
+        self.0.status(assoc.as_i32())
+    }
+
+    pub fn peer_addr_info(&self, assoc: AssocId, addr: SocketAddr) -> io::Result<PathInfo> {
+        self.0.peer_addr_info(assoc.as_i32(), &addr)
+    }
+
+    /// Path info of every peer address of `assoc`
+    pub fn paths(&self, assoc: AssocId) -> io::Result<Vec<PathInfo>> {
+        self.0.paths(assoc.as_i32())
+    }
+
+    pub fn assoc_stats(&self, assoc: AssocId) -> io::Result<AssocStats> {
+        self.0.assoc_stats(assoc.as_i32())
+    }
+
+    /// Select the peer address data of `assoc` is sent to by default
+    pub fn set_primary_addr(&self, assoc: AssocId, addr: SocketAddr) -> io::Result<()> {
+        self.0.set_primary_addr(assoc.as_i32(), &addr)
+    }
+
+    pub fn primary_addr(&self, assoc: AssocId) -> io::Result<SocketAddr> {
+        self.0.primary_addr(assoc.as_i32())
+    }
+
+    /// Ask the peer of `assoc` to use one of our local addresses as its primary
+    pub fn set_peer_primary_addr(&self, assoc: AssocId, addr: SocketAddr) -> io::Result<()> {
+        self.0.set_peer_primary_addr(assoc.as_i32(), &addr)
+    }
+
+    /// Require peers to authenticate chunks of type `chunk`, needs `net.sctp.auth_enable`
+    pub fn add_auth_chunk(&self, chunk: u8) -> io::Result<()> {
+        self.0.add_auth_chunk(chunk)
+    }
+
+    /// HMAC algorithms in order of preference, SHA-1 must be included
+    pub fn set_hmac_idents(&self, algos: &[HmacAlgo]) -> io::Result<()> {
+        self.0.set_hmac_idents(algos)
+    }
+
+    pub fn hmac_idents(&self) -> io::Result<Vec<HmacAlgo>> {
+        self.0.hmac_idents()
+    }
+
+    /// Add a shared key to `assoc`, or to the endpoint with `AssocId::future()`
+    pub fn add_auth_key(&self, assoc: AssocId, keynumber: u16, key: &[u8]) -> io::Result<()> {
+        self.0.add_auth_key(assoc.as_i32(), keynumber, key)
+    }
+
+    pub fn set_active_auth_key(&self, assoc: AssocId, keynumber: u16) -> io::Result<()> {
+        self.0.set_active_auth_key(assoc.as_i32(), keynumber)
+    }
+
+    pub fn active_auth_key(&self, assoc: AssocId) -> io::Result<u16> {
+        self.0.active_auth_key(assoc.as_i32())
+    }
+
+    pub fn delete_auth_key(&self, assoc: AssocId, keynumber: u16) -> io::Result<()> {
+        self.0.delete_auth_key(assoc.as_i32(), keynumber)
+    }
+
+    /// Chunk types the peer of `assoc` requires us to authenticate
+    pub fn peer_auth_chunks(&self, assoc: AssocId) -> io::Result<Vec<u8>> {
+        self.0.peer_auth_chunks(assoc.as_i32())
+    }
+
+    /// Chunk types we require the peer of `assoc` to authenticate
+    pub fn local_auth_chunks(&self, assoc: AssocId) -> io::Result<Vec<u8>> {
+        self.0.local_auth_chunks(assoc.as_i32())
+    }
+
     pub fn set_read_timeout(&self, dur: Option<Duration>) -> io::Result<()> {
         self.0.set_timeout(dur, libc::SO_SNDTIMEO)
     }
@@ -424,8 +1031,28 @@ impl SctpEndpoint {
         self.0.timeout(libc::SO_RCVTIMEO)
     }
 
+    /// Like `SctpStream::event_subscribe`
+    pub fn event_subscribe(&self, event: Event) -> io::Result<()> {
+        self.0.event_subscribe(event)
+    }
+
+    #[deprecated(note = "renamed to `event_subscribe`")]
     pub fn event_subsctibe(&self, event: Event) -> io::Result<()> {
-        self.0.event_subsctibe(event)
+        self.event_subscribe(event)
+    }
+
+    /// Like `SctpStream::event_unsubscribe`
+    pub fn event_unsubscribe(&self, event: Event) -> io::Result<()> {
+        self.0.event_unsubscribe(event)
+    }
+
+    /// Like `SctpStream::subscribed_events`
+    pub fn subscribed_events(&self) -> io::Result<Event> {
+        self.0.subscribed_events()
+    }
+
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.0.set_nonblocking(nonblocking)
     }
 
     pub fn try_clone(&self) -> io::Result<SctpStream> {
@@ -443,9 +1070,206 @@ impl AsRawFd for SctpEndpoint {
     }
 }
 
+impl AsFd for SctpEndpoint {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        unsafe { BorrowedFd::borrow_raw(self.as_raw_fd()) }
+    }
+}
+
 impl FromRawFd for SctpEndpoint {
     unsafe fn from_raw_fd(fd: RawFd) -> SctpEndpoint {
         let sock = Socket::from_raw_fd(fd);
         SctpEndpoint(sock)
     }
 }
+
+/// Create an SCTP socket and configure it before it is bound, connected or listening,
+/// for the options that only apply to associations set up afterwards
+pub struct SctpSocketBuilder {
+    sock: Socket,
+    ty: libc::c_int,
+    nonblocking: bool
+}
+
+impl SctpSocketBuilder {
+    /// One-to-one socket, finished with `connect`, `connectx` or `listen`
+    pub fn stream_v4() -> io::Result<SctpSocketBuilder> {
+        SctpSocketBuilder::new(libc::AF_INET, libc::SOCK_STREAM)
+    }
+
+    /// One-to-one socket, finished with `connect`, `connectx` or `listen`
+    pub fn stream_v6() -> io::Result<SctpSocketBuilder> {
+        SctpSocketBuilder::new(libc::AF_INET6, libc::SOCK_STREAM)
+    }
+
+    /// One-to-many socket, finished with `endpoint`
+    pub fn endpoint_v4() -> io::Result<SctpSocketBuilder> {
+        SctpSocketBuilder::new(libc::AF_INET, libc::SOCK_SEQPACKET)
+    }
+
+    /// One-to-many socket, finished with `endpoint`
+    pub fn endpoint_v6() -> io::Result<SctpSocketBuilder> {
+        SctpSocketBuilder::new(libc::AF_INET6, libc::SOCK_SEQPACKET)
+    }
+
+    fn new(family: libc::c_int, ty: libc::c_int) -> io::Result<SctpSocketBuilder> {
+        Ok(SctpSocketBuilder {
+            sock: Socket::new_raw(family, ty)?,
+            ty,
+            nonblocking: false
+        })
+    }
+
+    /// Streams and INIT retransmissions requested when associations are set up
+    pub fn init_params(self, params: &InitParams) -> io::Result<SctpSocketBuilder> {
+        self.sock.set_init_params(params)?;
+        Ok(self)
+    }
+
+    /// Subscribe to notifications, on top of the ones already subscribed (see `SctpStream::event_subscribe`)
+    pub fn event_subscribe(self, event: Event) -> io::Result<SctpSocketBuilder> {
+        self.sock.event_subscribe(event)?;
+        Ok(self)
+    }
+
+    /// Stop the given notifications
+    pub fn event_unsubscribe(self, event: Event) -> io::Result<SctpSocketBuilder> {
+        self.sock.event_unsubscribe(event)?;
+        Ok(self)
+    }
+
+    /// Retransmission timeout bounds for future associations
+    pub fn rto_info(self, info: &RtoInfo) -> io::Result<SctpSocketBuilder> {
+        self.sock.set_rto_info(sys::SCTP_FUTURE_ASSOC, info)?;
+        Ok(self)
+    }
+
+    /// Disable Nagle-like bundling of small messages (`SCTP_NODELAY`)
+    pub fn nodelay(self, nodelay: bool) -> io::Result<SctpSocketBuilder> {
+        self.sock.set_nodelay(nodelay)?;
+        Ok(self)
+    }
+
+    /// Add and remove local addresses automatically as interfaces change
+    pub fn auto_asconf(self, auto_asconf: bool) -> io::Result<SctpSocketBuilder> {
+        self.sock.set_auto_asconf(auto_asconf)?;
+        Ok(self)
+    }
+
+    /// `SO_SNDBUF`, sizes above `i32::MAX` are refused with `InvalidInput`
+    pub fn send_buffer_size(self, size: usize) -> io::Result<SctpSocketBuilder> {
+        self.sock.set_buffer_size(libc::SO_SNDBUF, size)?;
+        Ok(self)
+    }
+
+    /// `SO_RCVBUF`, sizes above `i32::MAX` are refused with `InvalidInput`
+    pub fn recv_buffer_size(self, size: usize) -> io::Result<SctpSocketBuilder> {
+        self.sock.set_buffer_size(libc::SO_RCVBUF, size)?;
+        Ok(self)
+    }
+
+    /// `SO_REUSEADDR`, bind to an address that still has associations shutting down
+    pub fn reuse_address(self, reuse: bool) -> io::Result<SctpSocketBuilder> {
+        self.sock.setsockopt(libc::SOL_SOCKET, libc::SO_REUSEADDR, reuse as libc::c_int)?;
+        Ok(self)
+    }
+
+    /// `SO_REUSEPORT`, let several sockets bind the same address and port
+    pub fn reuse_port(self, reuse: bool) -> io::Result<SctpSocketBuilder> {
+        self.sock.setsockopt(libc::SOL_SOCKET, libc::SO_REUSEPORT, reuse as libc::c_int)?;
+        Ok(self)
+    }
+
+    /// Only send and receive through the interface `ifname` (`SO_BINDTODEVICE`)
+    pub fn bind_device(self, ifname: &str) -> io::Result<SctpSocketBuilder> {
+        self.sock.setsockopt_bytes(libc::SOL_SOCKET, libc::SO_BINDTODEVICE, ifname.as_bytes())?;
+        Ok(self)
+    }
+
+    /// Connecting then returns as soon as the association setup is started
+    pub fn nonblocking(mut self, nonblocking: bool) -> io::Result<SctpSocketBuilder> {
+        self.sock.set_nonblocking(nonblocking)?;
+        self.nonblocking = nonblocking;
+        Ok(self)
+    }
+
+    /// Bind the local address, also before connecting
+    pub fn bind<A: ToSocketAddrs>(self, addr: A) -> io::Result<SctpSocketBuilder> {
+        let addr = parse_addr(addr)?;
+        self.sock.bind(&addr)?;
+        Ok(self)
+    }
+
+    /// Bind several local addresses, so a client association is multihomed on its side too.
+    /// They all share one port, a 0 port picks an ephemeral one
+    pub fn bindx<A: ToSocketAddrs>(self, addrs: &[A]) -> io::Result<SctpSocketBuilder> {
+        self.sock.bindx(&parse_addrs(addrs)?, BindOp::AddAddr)?;
+        Ok(self)
+    }
+
+    /// Finish as a one-to-one stream connected to `addr`
+    pub fn connect<A: ToSocketAddrs>(self, addr: A) -> io::Result<SctpStream> {
+        self.check_type(libc::SOCK_STREAM)?;
+
+        let addr = parse_addr(addr)?;
+
+        match self.sock.connect(&addr) {
+            Ok(()) => (),
+            Err(ref e) if self.nonblocking && e.raw_os_error() == Some(libc::EINPROGRESS) => (),
+            Err(e) => return Err(e)
+        }
+
+        Ok(SctpStream(self.sock))
+    }
+
+    /// Finish as a one-to-one stream connected to all of the peer's `addrs`
+    pub fn connectx<A: ToSocketAddrs>(self, addrs: &[A]) -> io::Result<SctpStream> {
+        self.check_type(libc::SOCK_STREAM)?;
+
+        match self.sock.connectx(&parse_addrs(addrs)?) {
+            Ok(_) => (),
+            Err(ref e) if self.nonblocking && e.raw_os_error() == Some(libc::EINPROGRESS) => (),
+            Err(e) => return Err(e)
+        }
+
+        Ok(SctpStream(self.sock))
+    }
+
+    /// Finish as a one-to-one listener
+    pub fn listen(self, backlog: i32) -> io::Result<SctpListener> {
+        self.check_type(libc::SOCK_STREAM)?;
+
+        self.sock.listen(backlog)?;
+
+        Ok(SctpListener(self.sock))
+    }
+
+    /// Start accepting associations, `backlog` bounds the ones still being set up
+    pub fn endpoint(self, backlog: i32) -> io::Result<SctpEndpoint> {
+        self.check_type(libc::SOCK_SEQPACKET)?;
+
+        self.sock.listen(backlog)?;
+
+        Ok(SctpEndpoint(self.sock))
+    }
+
+    fn check_type(&self, ty: libc::c_int) -> io::Result<()> {
+        if self.ty != ty {
+            let msg = if ty == libc::SOCK_STREAM {
+                "Not a one-to-one socket"
+            } else {
+                "Not a one-to-many socket"
+            };
+
+            return Err(Error::new(ErrorKind::InvalidInput, msg))
+        }
+
+        Ok(())
+    }
+}
+
+impl AsRawFd for SctpSocketBuilder {
+    fn as_raw_fd(&self) -> RawFd {
+        self.sock.as_raw_fd()
+    }
+}
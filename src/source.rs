@@ -0,0 +1,29 @@
+//! `mio` integration. The sockets must be switched to non-blocking mode with `set_nonblocking`
+//! (or created with `SctpStream::connect_nonblocking`) before they are registered.
+
+use std::io;
+use std::os::unix::io::AsRawFd;
+
+use mio::{Interest, Registry, Token};
+use mio::event::Source;
+use mio::unix::SourceFd;
+
+use sctp::{SctpStream, SctpListener, SctpEndpoint};
+
+macro_rules! impl_source {
+    ($($t:ident)*) => ($(impl Source for $t {
+        fn register(&mut self, registry: &Registry, token: Token, interests: Interest) -> io::Result<()> {
+            SourceFd(&self.as_raw_fd()).register(registry, token, interests)
+        }
+
+        fn reregister(&mut self, registry: &Registry, token: Token, interests: Interest) -> io::Result<()> {
+            SourceFd(&self.as_raw_fd()).reregister(registry, token, interests)
+        }
+
+        fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+            SourceFd(&self.as_raw_fd()).deregister(registry)
+        }
+    })*)
+}
+
+impl_source! { SctpStream SctpListener SctpEndpoint }
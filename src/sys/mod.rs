@@ -1,8 +1,8 @@
 #![allow(non_upper_case_globals)]
 #![allow(non_camel_case_types)]
+#![allow(clippy::missing_safety_doc)]
 
 use std::marker::PhantomData;
-use std::mem::transmute;
 use std::slice;
 use std::fmt;
 
@@ -19,12 +19,12 @@ impl<T> __IncompleteArrayField<T> {
 
     #[inline]
     pub unsafe fn as_ptr(&self) -> * const T {
-        transmute(self)
+        self as *const __IncompleteArrayField<T> as *const T
     }
 
     #[inline]
     pub unsafe fn as_mut_ptr(&mut self) -> *mut T {
-        transmute(self)
+        self as *mut __IncompleteArrayField<T> as *mut T
     }
 
     #[inline]
@@ -47,7 +47,7 @@ impl<T> fmt::Debug for __IncompleteArrayField<T> {
 impl <T> Clone for __IncompleteArrayField<T> {
     #[inline]
     fn clone(&self) -> Self {
-        Self::new()
+        *self
     }
 }
 
@@ -86,6 +86,10 @@ pub const SCTP_AUTH_DELETE_KEY: i32 = 25;
 pub const SCTP_PEER_AUTH_CHUNKS: i32 = 26;
 pub const SCTP_LOCAL_AUTH_CHUNKS: i32 = 27;
 pub const SCTP_GET_ASSOC_NUMBER: i32 = 28;
+pub const SCTP_AUTO_ASCONF: i32 = 30;
+pub const SCTP_RECVRCVINFO: i32 = 32;
+pub const SCTP_RECVNXTINFO: i32 = 33;
+pub const SCTP_DEFAULT_SNDINFO: i32 = 34;
 pub const SCTP_SOCKOPT_BINDX_ADD: i32 = 100;
 pub const SCTP_SOCKOPT_BINDX_REM: i32 = 101;
 pub const SCTP_SOCKOPT_PEELOFF: i32 = 102;
@@ -95,12 +99,17 @@ pub const SCTP_GET_LOCAL_ADDRS: i32 = 109;
 pub const SCTP_SOCKOPT_CONNECTX: i32 = 110;
 pub const SCTP_SOCKOPT_CONNECTX3: i32 = 111;
 pub const SCTP_GET_ASSOC_STATS: i32 = 112;
+pub const SCTP_EVENT: i32 = 127;
 
 pub const SCTP_BINDX_ADD_ADDR: i32 = 1;
 pub const SCTP_BINDX_REM_ADDR: i32 = 2;
 
 pub type sctp_assoc_t = i32;
 
+pub const SCTP_FUTURE_ASSOC: sctp_assoc_t = 0;
+pub const SCTP_CURRENT_ASSOC: sctp_assoc_t = 1;
+pub const SCTP_ALL_ASSOC: sctp_assoc_t = 2;
+
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
 pub struct sctp_initmsg {
@@ -146,11 +155,69 @@ pub union sctp_cmsg_data_t {
 #[derive(Debug, Clone)]
 pub enum sctp_cmsg_type {
     SCTP_INIT = 0,
-    SCTP_SNDRCV = 1
+    SCTP_SNDRCV = 1,
+    SCTP_SNDINFO = 2,
+    SCTP_RCVINFO = 3,
+    SCTP_NXTINFO = 4,
+    SCTP_PRINFO = 5,
+    SCTP_AUTHINFO = 6,
+    SCTP_DSTADDRV4 = 7,
+    SCTP_DSTADDRV6 = 8
 }
 
 pub type sctp_cmsg_t = u32;
 
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct sctp_sndinfo {
+    pub snd_sid: u16,
+    pub snd_flags: u16,
+    pub snd_ppid: u32,
+    pub snd_context: u32,
+    pub snd_assoc_id: sctp_assoc_t
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct sctp_rcvinfo {
+    pub rcv_sid: u16,
+    pub rcv_ssn: u16,
+    pub rcv_flags: u16,
+    pub rcv_ppid: u32,
+    pub rcv_tsn: u32,
+    pub rcv_cumtsn: u32,
+    pub rcv_context: u32,
+    pub rcv_assoc_id: sctp_assoc_t
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct sctp_nxtinfo {
+    pub nxt_sid: u16,
+    pub nxt_flags: u16,
+    pub nxt_ppid: u32,
+    pub nxt_length: u32,
+    pub nxt_assoc_id: sctp_assoc_t
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct sctp_prinfo {
+    pub pr_policy: u16,
+    pub pr_value: u32
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct sctp_authinfo {
+    pub auth_keynumber: u16
+}
+
+pub const SCTP_PR_SCTP_NONE: u16 = 0x0000;
+pub const SCTP_PR_SCTP_TTL: u16 = 0x0010;
+pub const SCTP_PR_SCTP_RTX: u16 = 0x0020;
+pub const SCTP_PR_SCTP_PRIO: u16 = 0x0030;
+
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
 pub struct sctp_assoc_change {
@@ -195,7 +262,8 @@ pub enum sctp_spc_state {
     SCTP_ADDR_REMOVED = 2,
     SCTP_ADDR_ADDED = 3,
     SCTP_ADDR_MADE_PRIM = 4,
-    SCTP_ADDR_CONFIRMED = 5
+    SCTP_ADDR_CONFIRMED = 5,
+    SCTP_ADDR_POTENTIALLY_FAILED = 6
 }
 
 #[repr(C)]
@@ -254,7 +322,9 @@ pub struct sctp_pdapi_event {
     pub pdapi_flags: u16,
     pub pdapi_length: u32,
     pub pdapi_indication: u32,
-    pub pdapi_assoc_id: sctp_assoc_t
+    pub pdapi_assoc_id: sctp_assoc_t,
+    pub pdapi_stream: u32,
+    pub pdapi_seq: u32
 }
 
 pub const SCTP_PARTIAL_DELIVERY_ABORTED: u32 = 0;
@@ -272,6 +342,8 @@ pub struct sctp_authkey_event {
 }
 
 pub const SCTP_AUTH_NEWKEY: u32 = 0;
+pub const SCTP_AUTH_FREE_KEY: u32 = 1;
+pub const SCTP_AUTH_NO_AUTH: u32 = 2;
 
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
@@ -283,7 +355,64 @@ pub struct sctp_sender_dry_event {
 }
 
 #[repr(C)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
+pub struct sctp_stream_reset_event {
+    pub strreset_type: u16,
+    pub strreset_flags: u16,
+    pub strreset_length: u32,
+    pub strreset_assoc_id: sctp_assoc_t,
+    pub strreset_stream_list: __IncompleteArrayField<u16>
+}
+
+pub const SCTP_STREAM_RESET_INCOMING_SSN: u16 = 0x0001;
+pub const SCTP_STREAM_RESET_OUTGOING_SSN: u16 = 0x0002;
+pub const SCTP_STREAM_RESET_DENIED: u16 = 0x0004;
+pub const SCTP_STREAM_RESET_FAILED: u16 = 0x0008;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct sctp_assoc_reset_event {
+    pub assocreset_type: u16,
+    pub assocreset_flags: u16,
+    pub assocreset_length: u32,
+    pub assocreset_assoc_id: sctp_assoc_t,
+    pub assocreset_local_tsn: u32,
+    pub assocreset_remote_tsn: u32
+}
+
+pub const SCTP_ASSOC_RESET_DENIED: u16 = 0x0004;
+pub const SCTP_ASSOC_RESET_FAILED: u16 = 0x0008;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct sctp_stream_change_event {
+    pub strchange_type: u16,
+    pub strchange_flags: u16,
+    pub strchange_length: u32,
+    pub strchange_assoc_id: sctp_assoc_t,
+    pub strchange_instrms: u16,
+    pub strchange_outstrms: u16
+}
+
+pub const SCTP_STREAM_CHANGE_DENIED: u16 = 0x0004;
+pub const SCTP_STREAM_CHANGE_FAILED: u16 = 0x0008;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct sctp_send_failed_event {
+    pub ssf_type: u16,
+    pub ssf_flags: u16,
+    pub ssf_length: u32,
+    pub ssf_error: u32,
+    pub ssfe_info: sctp_sndinfo,
+    pub ssf_assoc_id: sctp_assoc_t,
+    pub ssf_data: __IncompleteArrayField<u8>
+}
+
+/// The prefix every kernel accepts for `SCTP_EVENTS`, a kernel refuses anything longer than
+/// its own struct. The later event types are only switched through `SCTP_EVENT`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
 pub struct sctp_event_subscribe {
     pub sctp_data_io_event: u8,
     pub sctp_association_event: u8,
@@ -297,6 +426,14 @@ pub struct sctp_event_subscribe {
     pub sctp_sender_dry_event: u8
 }
 
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct sctp_event {
+    pub se_assoc_id: sctp_assoc_t,
+    pub se_type: u16,
+    pub se_on: u8
+}
+
 #[repr(C)]
 pub union sctp_notification {
     pub sn_header: sctp_notification_header,
@@ -308,7 +445,11 @@ pub union sctp_notification {
     pub sn_adaptation_event: sctp_adaptation_event,
     pub sn_pdapi_event: sctp_pdapi_event,
     pub sn_authkey_event: sctp_authkey_event,
-    pub sn_sender_dry_event: sctp_sender_dry_event
+    pub sn_sender_dry_event: sctp_sender_dry_event,
+    pub sn_strreset_event: sctp_stream_reset_event,
+    pub sn_assocreset_event: sctp_assoc_reset_event,
+    pub sn_strchange_event: sctp_stream_change_event,
+    pub sn_send_failed_event: sctp_send_failed_event
 }
 
 #[repr(C)]
@@ -331,7 +472,11 @@ pub enum sctp_sn_type {
     SCTP_PARTIAL_DELIVERY_EVENT = 32774,
     SCTP_ADAPTATION_INDICATION = 32775,
     SCTP_AUTHENTICATION_INDICATION = 32776,
-    SCTP_SENDER_DRY_EVENT = 32777
+    SCTP_SENDER_DRY_EVENT = 32777,
+    SCTP_STREAM_RESET_EVENT = 32778,
+    SCTP_ASSOC_RESET_EVENT = 32779,
+    SCTP_STREAM_CHANGE_EVENT = 32780,
+    SCTP_SEND_FAILED_EVENT = 32781
 }
 
 #[repr(C)]
@@ -366,16 +511,19 @@ pub struct sctp_assocparams {
     pub sasoc_cookie_life: u32
 }
 
-#[repr(C)]
+// Both are packed, aligned(4) in the kernel headers
+#[repr(C, packed(4))]
+#[derive(Clone, Copy)]
 pub struct sctp_setpeerprim {
-    sspp_assoc_id: sctp_assoc_t,
-    sspp_addr: libc::sockaddr_storage
+    pub sspp_assoc_id: sctp_assoc_t,
+    pub sspp_addr: libc::sockaddr_storage
 }
 
-#[repr(C)]
+#[repr(C, packed(4))]
+#[derive(Clone, Copy)]
 pub struct sctp_setprim {
-    ssp_assoc_id: sctp_assoc_t,
-    ssp_addr: libc::sockaddr_storage
+    pub ssp_assoc_id: sctp_assoc_t,
+    pub ssp_addr: libc::sockaddr_storage
 }
 
 #[repr(C)]
@@ -400,7 +548,9 @@ pub enum sctp_spp_flags {
     SPP_HB_TIME_IS_ZERO = 1<<7, /* Set HB delay to 0 */
 }
 
-#[repr(C)]
+// The kernel declares this packed, aligned(4): no padding between fields, tail padded to 4 bytes
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
 pub struct sctp_paddrparams {
     pub spp_assoc_id: sctp_assoc_t,
     pub spp_address: libc::sockaddr_storage,
@@ -408,7 +558,8 @@ pub struct sctp_paddrparams {
     pub spp_pathmaxrxt: u16,
     pub spp_pathmtu: u32,
     pub spp_sackdelay: u32,
-    pub spp_flags: u32
+    pub spp_flags: u32,
+    pub spp_padding: [u8; 2]
 }
 
 #[repr(C)]
@@ -458,7 +609,9 @@ pub struct sctp_assoc_value {
     pub assoc_value: u32
 }
 
-#[repr(C)]
+// Packed, aligned(4) in the kernel headers: the address follows the assoc id without padding
+#[repr(C, packed(4))]
+#[derive(Clone, Copy)]
 pub struct sctp_paddrinfo {
     pub spinfo_assoc_id: sctp_assoc_t,
     pub spinfo_address: libc::sockaddr_storage,
@@ -480,6 +633,7 @@ pub enum sctp_spinfo_state {
 }
 
 #[repr(C)]
+#[derive(Clone, Copy)]
 pub struct sctp_status {
     pub sstat_assoc_id: sctp_assoc_t,
     pub sstat_state: i32,
@@ -561,6 +715,13 @@ pub struct sctp_peeloff_arg_t {
     pub sd: libc::c_int
 }
 
+#[cfg(not(feature = "libsctp"))]
+mod native;
+
+#[cfg(not(feature = "libsctp"))]
+pub use self::native::*;
+
+#[cfg(feature = "libsctp")]
 extern "C" {
 
     pub fn sctp_bindx (
@@ -616,11 +777,11 @@ extern "C" {
         len: usize,
         to: *const libc::sockaddr,
         tolen: libc::socklen_t,
-        ppid: libc::uint32_t,
-        flags: libc::uint32_t,
-        stream_no: libc::uint16_t,
-        timetolive: libc::uint32_t,
-        context: libc::uint32_t
+        ppid: u32,
+        flags: u32,
+        stream_no: u16,
+        timetolive: u32,
+        context: u32
     ) -> libc::c_int;
 
     pub fn sctp_send(
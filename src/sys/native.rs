@@ -0,0 +1,362 @@
+//! Rust implementation of the libsctp helpers on top of the raw kernel socket options,
+//! with the same signatures and C-style return values as the `extern` declarations used
+//! with the `libsctp` feature.
+
+use std::io;
+use std::mem;
+use std::ptr;
+
+use libc;
+
+use super::*;
+
+unsafe fn set_errno(errno: libc::c_int) {
+    *libc::__errno_location() = errno;
+}
+
+fn last_errno() -> libc::c_int {
+    io::Error::last_os_error().raw_os_error().unwrap_or(0)
+}
+
+/// Total size of `addrcnt` packed `sockaddr_in`/`sockaddr_in6`, -1 on an unknown family
+unsafe fn addrs_size(addrs: *const libc::sockaddr, addrcnt: libc::c_int) -> libc::c_int {
+    let mut size = 0;
+    let mut addr = addrs as *const u8;
+
+    for _ in 0..addrcnt {
+        // Packed addresses are not necessarily aligned
+        let len = sctp_getaddrlen(ptr::read_unaligned(addr as *const libc::sa_family_t));
+        if len < 0 {
+            return -1
+        }
+
+        size += len;
+        addr = addr.offset(len as isize);
+    }
+
+    size
+}
+
+pub unsafe fn sctp_bindx(
+    sd: libc::c_int,
+    addrs: *const libc::sockaddr,
+    addrcnt: libc::c_int,
+    flags: libc::c_int
+) -> libc::c_int {
+    let opt = match flags {
+        SCTP_BINDX_ADD_ADDR => SCTP_SOCKOPT_BINDX_ADD,
+        SCTP_BINDX_REM_ADDR => SCTP_SOCKOPT_BINDX_REM,
+        _ => {
+            set_errno(libc::EINVAL);
+            return -1
+        }
+    };
+
+    let size = addrs_size(addrs, addrcnt);
+    if size < 0 {
+        return -1
+    }
+
+    libc::setsockopt(sd, SOL_SCTP, opt, addrs as *const libc::c_void, size as libc::socklen_t)
+}
+
+pub unsafe fn sctp_connectx(
+    sd: libc::c_int,
+    addrs: *const libc::sockaddr,
+    addrcnt: libc::c_int,
+    id: *mut sctp_assoc_t
+) -> libc::c_int {
+    let size = addrs_size(addrs, addrcnt);
+    if size < 0 {
+        return -1
+    }
+
+    let mut param = sctp_getaddrs_old {
+        assoc_id: 0,
+        addr_num: size,
+        addrs: addrs as *mut libc::sockaddr
+    };
+    let mut len = mem::size_of::<sctp_getaddrs_old>() as libc::socklen_t;
+
+    let ret = libc::getsockopt(sd, SOL_SCTP, SCTP_SOCKOPT_CONNECTX3, &mut param as *mut _ as *mut libc::c_void, &mut len);
+
+    // A non-blocking connect still hands out the association id
+    if ret == 0 || last_errno() == libc::EINPROGRESS {
+        if !id.is_null() {
+            *id = param.assoc_id;
+        }
+        return ret
+    }
+
+    if last_errno() != libc::ENOPROTOOPT {
+        return ret
+    }
+
+    // Kernels before 2.6.35 only know the setsockopt variant, returning the association id
+    let ret = libc::setsockopt(sd, SOL_SCTP, SCTP_SOCKOPT_CONNECTX, addrs as *const libc::c_void, size as libc::socklen_t);
+    if ret > 0 {
+        if !id.is_null() {
+            *id = ret;
+        }
+        return 0
+    }
+
+    ret
+}
+
+pub unsafe fn sctp_peeloff(
+    sd: libc::c_int,
+    assoc_id: sctp_assoc_t
+) -> libc::c_int {
+    let mut arg = sctp_peeloff_arg_t {
+        associd: assoc_id,
+        sd: 0
+    };
+    let mut len = mem::size_of::<sctp_peeloff_arg_t>() as libc::socklen_t;
+
+    let ret = libc::getsockopt(sd, SOL_SCTP, SCTP_SOCKOPT_PEELOFF, &mut arg as *mut _ as *mut libc::c_void, &mut len);
+    if ret < 0 {
+        return ret
+    }
+
+    arg.sd
+}
+
+/// The association id is passed to the kernel as the first field of `arg`
+pub unsafe fn sctp_opt_info(
+    sd: libc::c_int,
+    id: sctp_assoc_t,
+    opt: libc::c_int,
+    arg: *mut libc::c_void,
+    size: *mut libc::socklen_t
+) -> libc::c_int {
+    if *size as usize >= mem::size_of::<sctp_assoc_t>() {
+        ptr::write_unaligned(arg as *mut sctp_assoc_t, id);
+    }
+
+    libc::getsockopt(sd, SOL_SCTP, opt, arg, size)
+}
+
+/// Fetch the packed addresses into a malloc'ed buffer, growing it until the kernel stops
+/// answering ENOMEM. The addresses are moved to the start of the buffer, freed with `free`.
+/// Like libsctp, no buffer is handed out when there are no addresses
+unsafe fn getaddrs(
+    sd: libc::c_int,
+    id: sctp_assoc_t,
+    opt: libc::c_int,
+    addrs: *mut *mut libc::sockaddr
+) -> libc::c_int {
+    const CHUNK: usize = 4096;
+
+    let header = mem::size_of::<sctp_getaddrs>();
+    let mut size = CHUNK;
+    let mut buf = libc::malloc(size) as *mut u8;
+
+    if buf.is_null() {
+        return -1
+    }
+
+    let mut len;
+
+    loop {
+        (*(buf as *mut sctp_getaddrs)).assoc_id = id;
+        len = size as libc::socklen_t;
+
+        if libc::getsockopt(sd, SOL_SCTP, opt, buf as *mut libc::c_void, &mut len) == 0 {
+            break
+        }
+
+        if last_errno() != libc::ENOMEM {
+            libc::free(buf as *mut libc::c_void);
+            return -1
+        }
+
+        size += CHUNK;
+        let grown = libc::realloc(buf as *mut libc::c_void, size) as *mut u8;
+        if grown.is_null() {
+            libc::free(buf as *mut libc::c_void);
+            return -1
+        }
+        buf = grown;
+    }
+
+    let count = (*(buf as *const sctp_getaddrs)).addr_num as libc::c_int;
+
+    if count == 0 {
+        libc::free(buf as *mut libc::c_void);
+        *addrs = ptr::null_mut();
+        return 0
+    }
+
+    let len = (len as usize).saturating_sub(header);
+
+    ptr::copy(buf.add(header), buf, len);
+    *addrs = buf as *mut libc::sockaddr;
+
+    count
+}
+
+pub unsafe fn sctp_getpaddrs(
+    sd: libc::c_int,
+    id: sctp_assoc_t,
+    addrs: *mut *mut libc::sockaddr
+) -> libc::c_int {
+    getaddrs(sd, id, SCTP_GET_PEER_ADDRS, addrs)
+}
+
+pub unsafe fn sctp_freepaddrs(
+    addrs: *mut libc::sockaddr
+) -> libc::c_int {
+    libc::free(addrs as *mut libc::c_void);
+    0
+}
+
+pub unsafe fn sctp_getladdrs(
+    sd: libc::c_int,
+    id: sctp_assoc_t,
+    addrs: *mut *mut libc::sockaddr
+) -> libc::c_int {
+    getaddrs(sd, id, SCTP_GET_LOCAL_ADDRS, addrs)
+}
+
+pub unsafe fn sctp_freeladdrs(
+    addrs: *mut libc::sockaddr
+) -> libc::c_int {
+    libc::free(addrs as *mut libc::c_void);
+    0
+}
+
+/// Control buffer for a single SCTP_SNDRCV message, in u64 to keep `cmsghdr` aligned
+type SndRcvControl = [u64; 8];
+
+#[allow(clippy::too_many_arguments)]
+pub unsafe fn sctp_sendmsg(
+    sd: libc::c_int,
+    msg: *const libc::c_void,
+    len: usize,
+    to: *const libc::sockaddr,
+    tolen: libc::socklen_t,
+    ppid: u32,
+    flags: u32,
+    stream_no: u16,
+    timetolive: u32,
+    context: u32
+) -> libc::c_int {
+    let mut sinfo: sctp_sndrcvinfo = mem::zeroed();
+    sinfo.sinfo_ppid = ppid;
+    sinfo.sinfo_flags = flags as u16;
+    sinfo.sinfo_stream = stream_no;
+    sinfo.sinfo_timetolive = timetolive;
+    sinfo.sinfo_context = context;
+
+    sendmsg_sndrcv(sd, msg, len, to, tolen, &sinfo, 0)
+}
+
+pub unsafe fn sctp_send(
+    sd: libc::c_int,
+    msg : *const libc::c_void,
+    len : libc::size_t,
+    sinfo : *const sctp_sndrcvinfo,
+    flags: libc::c_int
+) -> libc::c_int {
+    sendmsg_sndrcv(sd, msg, len, ptr::null(), 0, &*sinfo, flags)
+}
+
+unsafe fn sendmsg_sndrcv(
+    sd: libc::c_int,
+    msg: *const libc::c_void,
+    len: usize,
+    to: *const libc::sockaddr,
+    tolen: libc::socklen_t,
+    sinfo: &sctp_sndrcvinfo,
+    flags: libc::c_int
+) -> libc::c_int {
+    let mut control: SndRcvControl = mem::zeroed();
+    let mut iov = libc::iovec {
+        iov_base: msg as *mut libc::c_void,
+        iov_len: len
+    };
+
+    let mut outmsg: libc::msghdr = mem::zeroed();
+    outmsg.msg_name = to as *mut libc::c_void;
+    outmsg.msg_namelen = tolen;
+    outmsg.msg_iov = &mut iov;
+    outmsg.msg_iovlen = 1;
+    outmsg.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+    outmsg.msg_controllen = libc::CMSG_SPACE(mem::size_of::<sctp_sndrcvinfo>() as libc::c_uint) as _;
+
+    let cmsg = libc::CMSG_FIRSTHDR(&outmsg);
+    (*cmsg).cmsg_level = IPPROTO_SCTP;
+    (*cmsg).cmsg_type = sctp_cmsg_type::SCTP_SNDRCV as libc::c_int;
+    (*cmsg).cmsg_len = libc::CMSG_LEN(mem::size_of::<sctp_sndrcvinfo>() as libc::c_uint) as _;
+    ptr::write_unaligned(libc::CMSG_DATA(cmsg) as *mut sctp_sndrcvinfo, *sinfo);
+
+    libc::sendmsg(sd, &outmsg, flags) as libc::c_int
+}
+
+pub unsafe fn sctp_recvmsg(
+    sd: libc::c_int,
+    msg: *mut libc::c_void,
+    len: libc::size_t,
+    from: *mut libc::sockaddr,
+    fromlen: *mut libc::socklen_t,
+    sinfo: *mut sctp_sndrcvinfo,
+    msg_flags: *mut libc::c_int
+) -> libc::c_int {
+    let mut control: SndRcvControl = mem::zeroed();
+    let mut iov = libc::iovec {
+        iov_base: msg,
+        iov_len: len
+    };
+
+    let mut inmsg: libc::msghdr = mem::zeroed();
+    inmsg.msg_name = from as *mut libc::c_void;
+    inmsg.msg_namelen = if fromlen.is_null() { 0 } else { *fromlen };
+    inmsg.msg_iov = &mut iov;
+    inmsg.msg_iovlen = 1;
+    inmsg.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+    inmsg.msg_controllen = mem::size_of::<SndRcvControl>() as _;
+
+    let flags = if msg_flags.is_null() { 0 } else { *msg_flags };
+
+    let ret = libc::recvmsg(sd, &mut inmsg, flags);
+    if ret < 0 {
+        return ret as libc::c_int
+    }
+
+    if !fromlen.is_null() {
+        *fromlen = inmsg.msg_namelen;
+    }
+
+    if !msg_flags.is_null() {
+        *msg_flags = inmsg.msg_flags;
+    }
+
+    if !sinfo.is_null() {
+        let mut cmsg = libc::CMSG_FIRSTHDR(&inmsg);
+
+        while !cmsg.is_null() {
+            if (*cmsg).cmsg_level == IPPROTO_SCTP
+                && (*cmsg).cmsg_type == sctp_cmsg_type::SCTP_SNDRCV as libc::c_int
+                && (*cmsg).cmsg_len as usize >= libc::CMSG_LEN(mem::size_of::<sctp_sndrcvinfo>() as libc::c_uint) as usize {
+                *sinfo = ptr::read_unaligned(libc::CMSG_DATA(cmsg) as *const sctp_sndrcvinfo);
+                break
+            }
+
+            cmsg = libc::CMSG_NXTHDR(&inmsg, cmsg);
+        }
+    }
+
+    ret as libc::c_int
+}
+
+pub unsafe fn sctp_getaddrlen(family: libc::sa_family_t) -> libc::c_int {
+    match family as libc::c_int {
+        libc::AF_INET => mem::size_of::<libc::sockaddr_in>() as libc::c_int,
+        libc::AF_INET6 => mem::size_of::<libc::sockaddr_in6>() as libc::c_int,
+        _ => {
+            set_errno(libc::EINVAL);
+            -1
+        }
+    }
+}
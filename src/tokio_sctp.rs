@@ -0,0 +1,110 @@
+//! Tokio versions of the SCTP sockets, driven by `tokio::io::unix::AsyncFd`.
+//!
+//! They have to be created from within a Tokio runtime with IO enabled. The wrapped socket
+//! stays reachable through `get_ref` for the synchronous options.
+
+use std::io::{self, Read, Write};
+use std::net::Shutdown;
+use std::os::unix::io::AsRawFd;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::io::unix::AsyncFd;
+
+use sctp::{SctpStream, SctpListener, SctpEndpoint};
+use async_sctp::Registered;
+
+impl<T: AsRawFd> Registered for AsyncFd<T> {
+    type Socket = T;
+
+    fn register(socket: T) -> io::Result<AsyncFd<T>> {
+        // The sockets own their descriptor and only close it on drop, so it stays open and
+        // unchanged for as long as the `AsyncFd` holds them
+        unsafe { AsyncFd::register(socket) }.map_err(io::Error::from)
+    }
+
+    fn get_ref(&self) -> &T {
+        AsyncFd::get_ref(self)
+    }
+
+    fn poll_writable(&self, cx: &mut Context) -> Poll<io::Result<()>> {
+        self.poll_write_ready(cx).map_ok(|_| ())
+    }
+
+    fn poll_read_with<R, F>(&self, cx: &mut Context, mut f: F) -> Poll<io::Result<R>>
+        where F: FnMut(&T) -> io::Result<R>
+    {
+        loop {
+            let mut guard = ready!(self.poll_read_ready(cx))?;
+
+            if let Ok(result) = guard.try_io(|inner| f(inner.get_ref())) {
+                return Poll::Ready(result)
+            }
+        }
+    }
+
+    fn poll_write_with<R, F>(&self, cx: &mut Context, mut f: F) -> Poll<io::Result<R>>
+        where F: FnMut(&T) -> io::Result<R>
+    {
+        loop {
+            let mut guard = ready!(self.poll_write_ready(cx))?;
+
+            if let Ok(result) = guard.try_io(|inner| f(inner.get_ref())) {
+                return Poll::Ready(result)
+            }
+        }
+    }
+}
+
+pub struct AsyncSctpStream(AsyncFd<SctpStream>);
+
+pub struct AsyncSctpListener(AsyncFd<SctpListener>);
+
+pub struct AsyncSctpEndpoint(AsyncFd<SctpEndpoint>);
+
+impl_async_sctp! { AsyncSctpStream, AsyncSctpListener, AsyncSctpEndpoint }
+
+impl AsyncSctpStream {
+    pub fn into_inner(self) -> SctpStream {
+        self.0.into_inner()
+    }
+}
+
+impl AsyncRead for AsyncSctpStream {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context, buf: &mut ReadBuf) -> Poll<io::Result<()>> {
+        let read = {
+            let unfilled = buf.initialize_unfilled();
+            ready!(self.0.poll_read_with(cx, |mut stream| stream.read(unfilled)))?
+        };
+
+        buf.advance(read);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncWrite for AsyncSctpStream {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<io::Result<usize>> {
+        self.0.poll_write_with(cx, |mut stream| stream.write(buf))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context) -> Poll<io::Result<()>> {
+        Poll::Ready(self.get_ref().shutdown(Shutdown::Write))
+    }
+}
+
+impl AsyncSctpListener {
+    pub fn into_inner(self) -> SctpListener {
+        self.0.into_inner()
+    }
+}
+
+impl AsyncSctpEndpoint {
+    pub fn into_inner(self) -> SctpEndpoint {
+        self.0.into_inner()
+    }
+}
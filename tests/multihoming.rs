@@ -0,0 +1,27 @@
+extern crate sctp;
+
+use std::net::{IpAddr, Ipv4Addr};
+
+use sctp::{SctpListener, SctpStream};
+
+// Needs a kernel with SCTP support, run with `cargo test -- --ignored`
+#[test]
+#[ignore]
+fn connectx_from_binds_every_local_address() {
+    let listener = SctpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addrs().unwrap()[0].port();
+
+    let stream = SctpStream::connectx_from(&["127.0.0.2:0", "127.0.0.3:0"], &[("127.0.0.1", port)]).unwrap();
+    let (accepted, _) = listener.accept().unwrap();
+
+    let mut local: Vec<IpAddr> = stream.local_addrs().unwrap().iter().map(|addr| addr.ip()).collect();
+    local.sort();
+    assert_eq!(local, vec![IpAddr::V4(Ipv4Addr::new(127, 0, 0, 2)), IpAddr::V4(Ipv4Addr::new(127, 0, 0, 3))]);
+
+    let local_port = stream.local_addrs().unwrap()[0].port();
+    assert!(stream.local_addrs().unwrap().iter().all(|addr| addr.port() == local_port));
+
+    let mut peer: Vec<IpAddr> = accepted.peer_addrs().unwrap().iter().map(|addr| addr.ip()).collect();
+    peer.sort();
+    assert_eq!(peer, local);
+}